@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use crate::{
+    decimal::{Px, Sz},
+    errors::Error,
+    exchange::order::{ClientOrder, ClientOrderRequest},
+    meta::{Meta, SpotMeta},
+    prelude::*,
+    rules::{AssetRule, Rounding},
+};
+
+const SPOT_ASSET_OFFSET: u32 = 10_000;
+const PERP_DEX_ASSET_OFFSET: u32 = 100_000;
+const PERP_DEX_ASSET_STRIDE: u32 = 10_000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssetInfo {
+    pub asset: u32,
+    /// Name used by the API and websocket feeds: `"ETH"`, `"PURR/USDC"`, `"@107"` or
+    /// `"dex:COIN"` for builder-deployed perps.
+    pub name: String,
+    pub sz_decimals: u32,
+    /// `None` for spot assets.
+    pub max_leverage: Option<u32>,
+    pub only_isolated: bool,
+    pub is_spot: bool,
+    /// The builder-deployed dex the perp is listed on, `None` for the default dex and spot.
+    pub dex: Option<String>,
+}
+
+impl AssetInfo {
+    pub fn rule(&self) -> AssetRule {
+        AssetRule {
+            sz_decimals: self.sz_decimals,
+            is_spot: self.is_spot,
+        }
+    }
+}
+
+/// Maps coin names to asset indices and back for perps, spot pairs and builder-deployed perp
+/// dexes.
+///
+/// Perps on the default dex use their universe index. Spot pairs use `10000 + index` and can
+/// be named either `BASE/QUOTE` or `@index`. Perps on the builder dex with index `d` use
+/// `100000 + d * 10000 + index` and are named `dex:COIN`.
+#[derive(Clone, Debug, Default)]
+pub struct AssetResolver {
+    assets: HashMap<u32, AssetInfo>,
+    names: HashMap<String, u32>,
+}
+
+impl AssetResolver {
+    pub fn new(meta: &Meta, spot_meta: Option<&SpotMeta>) -> AssetResolver {
+        let mut resolver = AssetResolver::default();
+        resolver.add_perps(0, None, meta);
+        if let Some(spot_meta) = spot_meta {
+            resolver.add_spot_meta(spot_meta);
+        }
+        resolver
+    }
+
+    /// Registers the perps of a builder-deployed dex. `dex_index` is the dex's position in the
+    /// `perpDexs` list, where index 0 is the default dex.
+    pub fn add_perp_dex(&mut self, dex_index: u32, dex: &str, meta: &Meta) {
+        let offset = PERP_DEX_ASSET_OFFSET + dex_index * PERP_DEX_ASSET_STRIDE;
+        self.add_perps(offset, Some(dex), meta);
+    }
+
+    fn add_perps(&mut self, offset: u32, dex: Option<&str>, meta: &Meta) {
+        for (index, asset_meta) in meta.universe.iter().enumerate() {
+            let name = match dex {
+                Some(dex) if !asset_meta.name.contains(':') => {
+                    format!("{dex}:{}", asset_meta.name)
+                }
+                _ => asset_meta.name.clone(),
+            };
+            self.insert(
+                AssetInfo {
+                    asset: offset + index as u32,
+                    name,
+                    sz_decimals: asset_meta.sz_decimals,
+                    max_leverage: Some(asset_meta.max_leverage as u32),
+                    only_isolated: asset_meta.only_isolated.unwrap_or(false),
+                    is_spot: false,
+                    dex: dex.map(str::to_string),
+                },
+                &[],
+            );
+        }
+    }
+
+    pub fn add_spot_meta(&mut self, spot_meta: &SpotMeta) {
+        let tokens: HashMap<usize, (&str, u32)> = spot_meta
+            .tokens
+            .iter()
+            .map(|token| (token.index, (token.name.as_str(), token.sz_decimals.into())))
+            .collect();
+
+        for pair in spot_meta.universe.iter() {
+            let (Some(&(base, sz_decimals)), Some(&(quote, _))) =
+                (tokens.get(&pair.tokens[0]), tokens.get(&pair.tokens[1]))
+            else {
+                continue;
+            };
+            self.insert(
+                AssetInfo {
+                    asset: SPOT_ASSET_OFFSET + pair.index as u32,
+                    name: pair.name.clone(),
+                    sz_decimals,
+                    max_leverage: None,
+                    only_isolated: false,
+                    is_spot: true,
+                    dex: None,
+                },
+                &[format!("{base}/{quote}"), format!("@{}", pair.index)],
+            );
+        }
+    }
+
+    fn insert(&mut self, info: AssetInfo, aliases: &[String]) {
+        for alias in aliases {
+            self.names.insert(alias.clone(), info.asset);
+        }
+        self.names.insert(info.name.clone(), info.asset);
+        self.assets.insert(info.asset, info);
+    }
+
+    /// Asset index for `coin`, accepting every alias of an asset.
+    pub fn asset(&self, coin: &str) -> Result<u32> {
+        self.names.get(coin).copied().ok_or(Error::AssetNotFound)
+    }
+
+    pub fn info(&self, coin: &str) -> Option<&AssetInfo> {
+        self.names
+            .get(coin)
+            .and_then(|asset| self.assets.get(asset))
+    }
+
+    pub fn info_by_asset(&self, asset: u32) -> Option<&AssetInfo> {
+        self.assets.get(&asset)
+    }
+
+    /// The name the API uses for `asset`.
+    pub fn name(&self, asset: u32) -> Option<&str> {
+        self.assets.get(&asset).map(|info| info.name.as_str())
+    }
+
+    pub fn round_px(&self, coin: &str, px: Px, rounding: Rounding) -> Result<Px> {
+        let info = self.info(coin).ok_or(Error::AssetNotFound)?;
+        Ok(info.rule().round_px(px, rounding))
+    }
+
+    pub fn round_sz(&self, coin: &str, sz: Sz, rounding: Rounding) -> Result<Sz> {
+        let info = self.info(coin).ok_or(Error::AssetNotFound)?;
+        Ok(info.rule().round_sz(sz, rounding))
+    }
+
+    /// Checks the limit price, trigger price and size of `order` before it is signed.
+    pub fn validate(&self, order: &ClientOrderRequest) -> Result<()> {
+        let rule = self
+            .info_by_asset(order.asset)
+            .ok_or(Error::AssetNotFound)?
+            .rule();
+        rule.validate_px(order.limit_px)?;
+        rule.validate_sz(order.sz)?;
+        if let ClientOrder::Trigger(trigger) = &order.order_type {
+            rule.validate_px(trigger.trigger_px)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolver() -> AssetResolver {
+        let meta: Meta = serde_json::from_str(
+            r#"{"universe":[
+                {"name":"BTC","szDecimals":5,"maxLeverage":40},
+                {"name":"ETH","szDecimals":4,"maxLeverage":25,"onlyIsolated":false}
+            ]}"#,
+        )
+        .unwrap();
+        let spot_meta: SpotMeta = serde_json::from_str(
+            r#"{"universe":[
+                {"tokens":[1,0],"name":"PURR/USDC","index":0,"isCanonical":true},
+                {"tokens":[150,0],"name":"@107","index":107,"isCanonical":false}
+            ],"tokens":[
+                {"name":"USDC","szDecimals":8,"weiDecimals":8,"index":0,"tokenId":"0x6d1e7cde53ba9467b783cb7c530ce054","isCanonical":true},
+                {"name":"PURR","szDecimals":0,"weiDecimals":5,"index":1,"tokenId":"0xc1fb593aeffbeb02f85e0308e9956a90","isCanonical":true},
+                {"name":"HYPE","szDecimals":2,"weiDecimals":8,"index":150,"tokenId":"0x0d01dc56dcaaca66ad901c959b4011ec","isCanonical":false}
+            ]}"#,
+        )
+        .unwrap();
+        let dex_meta: Meta = serde_json::from_str(
+            r#"{"universe":[{"name":"xyz:XYZ100","szDecimals":4,"maxLeverage":20,"onlyIsolated":true}]}"#,
+        )
+        .unwrap();
+
+        let mut resolver = AssetResolver::new(&meta, Some(&spot_meta));
+        resolver.add_perp_dex(1, "xyz", &dex_meta);
+        resolver
+    }
+
+    #[test]
+    fn test_resolves_every_asset_class() -> Result<()> {
+        let resolver = resolver();
+        assert_eq!(resolver.asset("ETH")?, 1);
+        assert_eq!(resolver.asset("PURR/USDC")?, 10000);
+        assert_eq!(resolver.asset("@0")?, 10000);
+        assert_eq!(resolver.asset("HYPE/USDC")?, 10107);
+        assert_eq!(resolver.asset("@107")?, 10107);
+        assert_eq!(resolver.asset("xyz:XYZ100")?, 110000);
+        assert!(resolver.asset("DOGE").is_err());
+
+        assert_eq!(resolver.name(10107), Some("@107"));
+        assert_eq!(resolver.name(110000), Some("xyz:XYZ100"));
+
+        let hype = resolver.info("HYPE/USDC").unwrap();
+        assert!(hype.is_spot);
+        assert_eq!(hype.sz_decimals, 2);
+        assert_eq!(hype.max_leverage, None);
+
+        let xyz = resolver.info_by_asset(110000).unwrap();
+        assert_eq!(xyz.max_leverage, Some(20));
+        assert!(xyz.only_isolated);
+        Ok(())
+    }
+}
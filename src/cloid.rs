@@ -0,0 +1,128 @@
+use std::{fmt, str::FromStr};
+
+use alloy::primitives::keccak256;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use utoipa::{
+    openapi::{schema::Type, ObjectBuilder, RefOr, Schema},
+    PartialSchema, ToSchema,
+};
+use uuid::Uuid;
+
+use crate::{errors::Error, prelude::*};
+
+/// A client order id: 16 bytes, sent and received as `0x` followed by 32 hex digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Cloid([u8; 16]);
+
+impl Cloid {
+    pub const fn from_bytes(bytes: [u8; 16]) -> Cloid {
+        Cloid(bytes)
+    }
+
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    pub fn random() -> Cloid {
+        Cloid(rand::random())
+    }
+
+    /// A cloid derived from a strategy `namespace` and a per-order `counter`: the first 8 bytes
+    /// hash the namespace, the last 8 hold the counter. Restarting a strategy with the same
+    /// namespace and counter reproduces its ids, and [`Cloid::counter`] maps fills and order
+    /// updates back to the order.
+    pub fn from_namespace(namespace: &str, counter: u64) -> Cloid {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&keccak256(namespace)[..8]);
+        bytes[8..].copy_from_slice(&counter.to_be_bytes());
+        Cloid(bytes)
+    }
+
+    /// The counter this cloid was generated with, if it belongs to `namespace`.
+    pub fn counter(&self, namespace: &str) -> Option<u64> {
+        if self.0[..8] != keccak256(namespace)[..8] {
+            return None;
+        }
+        let mut counter = [0u8; 8];
+        counter.copy_from_slice(&self.0[8..]);
+        Some(u64::from_be_bytes(counter))
+    }
+
+    pub fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.0))
+    }
+}
+
+impl From<Uuid> for Cloid {
+    fn from(uuid: Uuid) -> Cloid {
+        Cloid(uuid.into_bytes())
+    }
+}
+
+impl FromStr for Cloid {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Cloid> {
+        let invalid = || Error::InvalidCloid(s.to_string());
+        let digits = s.strip_prefix("0x").ok_or_else(invalid)?;
+        let mut bytes = [0u8; 16];
+        hex::decode_to_slice(digits, &mut bytes).map_err(|_| invalid())?;
+        Ok(Cloid(bytes))
+    }
+}
+
+impl fmt::Display for Cloid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+impl Serialize for Cloid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+impl<'de> Deserialize<'de> for Cloid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Cloid, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+impl PartialSchema for Cloid {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .pattern(Some("^0x[0-9a-fA-F]{32}$"))
+            .into()
+    }
+}
+
+impl ToSchema for Cloid {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cloid_parsing_and_namespaces() -> Result<()> {
+        let cloid: Cloid = "0x00000000000000000000000000000002".parse()?;
+        assert_eq!(cloid.to_hex(), "0x00000000000000000000000000000002");
+        assert_eq!(
+            serde_json::from_str::<Cloid>(r#""0x00000000000000000000000000000002""#).unwrap(),
+            cloid
+        );
+        assert!("0x0002".parse::<Cloid>().is_err());
+        assert!("00000000000000000000000000000002".parse::<Cloid>().is_err());
+        assert!("0x0000000000000000000000000000000g"
+            .parse::<Cloid>()
+            .is_err());
+
+        let cloid = Cloid::from_namespace("grid-eth", 42);
+        assert_eq!(cloid.to_hex().parse::<Cloid>()?, cloid);
+        assert_eq!(cloid.counter("grid-eth"), Some(42));
+        assert_eq!(cloid.counter("grid-btc"), None);
+        Ok(())
+    }
+}
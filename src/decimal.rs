@@ -0,0 +1,186 @@
+use std::{fmt, str::FromStr};
+
+use rust_decimal::Decimal;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use utoipa::{
+    openapi::{schema::Type, ObjectBuilder, RefOr, Schema},
+    PartialSchema, ToSchema,
+};
+
+use crate::{
+    errors::Error,
+    helpers::{float_to_string_for_hashing, WIRE_DECIMALS},
+    prelude::*,
+};
+
+/// Formats `value` the way the exchange hashes numbers: at most 8 decimals and no trailing
+/// zeros. Matches [`float_to_string_for_hashing`] byte for byte.
+fn decimal_to_wire(value: Decimal) -> String {
+    let value = value.round_dp(WIRE_DECIMALS.into()).normalize();
+    if value.is_zero() {
+        "0".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+macro_rules! decimal_newtype {
+    ($(#[$doc:meta])* $name:ident) => {
+        $(#[$doc])*
+        #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(Decimal);
+
+        impl $name {
+            pub const ZERO: $name = $name(Decimal::ZERO);
+
+            pub const fn new(value: Decimal) -> $name {
+                $name(value)
+            }
+
+            pub const fn value(&self) -> Decimal {
+                self.0
+            }
+
+            /// The string sent to the exchange and included in action hashes.
+            pub fn to_wire(&self) -> String {
+                decimal_to_wire(self.0)
+            }
+        }
+
+        impl From<Decimal> for $name {
+            fn from(value: Decimal) -> $name {
+                $name(value)
+            }
+        }
+
+        impl From<$name> for Decimal {
+            fn from(value: $name) -> Decimal {
+                value.0
+            }
+        }
+
+        impl TryFrom<f64> for $name {
+            type Error = Error;
+
+            /// Rounds to 8 decimals exactly like the legacy `f64` wire formatting did.
+            fn try_from(value: f64) -> Result<$name> {
+                float_to_string_for_hashing(value).parse()
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(value: &str) -> Result<$name> {
+                Decimal::from_str_exact(value.trim())
+                    .or_else(|_| Decimal::from_scientific(value.trim()))
+                    .map($name)
+                    .map_err(|_| Error::FloatStringParse)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.to_wire())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+                s.serialize_str(&self.to_wire())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(d: D) -> std::result::Result<$name, D::Error> {
+                d.deserialize_any(DecimalVisitor).map($name)
+            }
+        }
+
+        impl PartialSchema for $name {
+            fn schema() -> RefOr<Schema> {
+                ObjectBuilder::new().schema_type(Type::String).into()
+            }
+        }
+
+        impl ToSchema for $name {}
+    };
+}
+
+decimal_newtype!(
+    /// A price, kept as a decimal so it round-trips the exchange's strings without loss.
+    Px
+);
+
+decimal_newtype!(
+    /// A size, kept as a decimal so it round-trips the exchange's strings without loss.
+    /// Signed sizes (e.g. position `szi`) are negative for shorts.
+    Sz
+);
+
+struct DecimalVisitor;
+
+impl de::Visitor<'_> for DecimalVisitor {
+    type Value = Decimal;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a decimal number or numeric string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<Decimal, E> {
+        Px::from_str(v).map(Into::into).map_err(E::custom)
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> std::result::Result<Decimal, E> {
+        Px::try_from(v).map(Into::into).map_err(E::custom)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> std::result::Result<Decimal, E> {
+        Ok(Decimal::from(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> std::result::Result<Decimal, E> {
+        Ok(Decimal::from(v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wire_format_matches_float_formatting() -> Result<()> {
+        for x in [
+            0.,
+            -0.,
+            0.00076,
+            0.00000001,
+            0.12345678,
+            87654321.12345678,
+            987654321.0,
+            87654321.1234,
+            1891.4,
+            -3.5,
+            0.000000005,
+            123456.123456789,
+        ] {
+            assert_eq!(Px::try_from(x)?.to_wire(), float_to_string_for_hashing(x));
+            assert_eq!(Sz::try_from(x)?.to_wire(), float_to_string_for_hashing(x));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn parses_strings_and_numbers() -> Result<()> {
+        assert_eq!("2000.0".parse::<Px>()?.to_wire(), "2000");
+        assert_eq!("-0.0100".parse::<Sz>()?.to_wire(), "-0.01");
+        assert!("abc".parse::<Px>().is_err());
+
+        let px: Px =
+            serde_json::from_str("\"0.00001234\"").map_err(|e| Error::JsonParse(e.to_string()))?;
+        assert_eq!(px.to_wire(), "0.00001234");
+        let sz: Sz = serde_json::from_str("12").map_err(|e| Error::JsonParse(e.to_string()))?;
+        assert_eq!(sz, Sz::new(Decimal::from(12)));
+        Ok(())
+    }
+}
@@ -1,12 +1,22 @@
+use std::collections::BTreeMap;
+
 use alloy::{
     dyn_abi::Eip712Domain,
     primitives::{keccak256, B256},
 };
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
 pub(crate) trait Eip712 {
     fn domain(&self) -> Eip712Domain;
     fn struct_hash(&self) -> B256;
 
+    /// Type string of the primary type, e.g. `Mail(address from,string contents)`.
+    fn encode_type(&self) -> &'static str;
+
+    /// Values of the primary type's fields, keyed by the names used in `encode_type`.
+    fn message(&self) -> Value;
+
     fn eip712_signing_hash(&self) -> B256 {
         let mut digest_input = [0u8; 2 + 32 + 32];
         digest_input[0] = 0x19;
@@ -15,4 +25,118 @@ pub(crate) trait Eip712 {
         digest_input[34..66].copy_from_slice(&self.struct_hash()[..]);
         keccak256(digest_input)
     }
+
+    fn typed_data(&self) -> TypedData {
+        let (primary_type, fields) = parse_encode_type(self.encode_type());
+        let domain = self.domain();
+
+        let mut types = BTreeMap::new();
+        types.insert(
+            "EIP712Domain".to_string(),
+            vec![
+                TypedDataField::new("name", "string"),
+                TypedDataField::new("version", "string"),
+                TypedDataField::new("chainId", "uint256"),
+                TypedDataField::new("verifyingContract", "address"),
+            ],
+        );
+        types.insert(primary_type.to_string(), fields);
+
+        TypedData {
+            domain: json!({
+                "name": domain.name,
+                "version": domain.version,
+                "chainId": domain.chain_id.map(|id| id.to::<u64>()),
+                "verifyingContract": domain.verifying_contract,
+            }),
+            types,
+            primary_type: primary_type.to_string(),
+            message: self.message(),
+        }
+    }
+}
+
+fn parse_encode_type(encode_type: &str) -> (&str, Vec<TypedDataField>) {
+    let (primary_type, fields) = encode_type
+        .trim_end_matches(')')
+        .split_once('(')
+        .unwrap_or((encode_type, ""));
+    let fields = fields
+        .split(',')
+        .filter_map(|field| field.split_once(' '))
+        .map(|(r#type, name)| TypedDataField::new(name, r#type))
+        .collect();
+    (primary_type, fields)
+}
+
+/// An `eth_signTypedData_v4` document, for wallets that show users what they sign instead of
+/// signing a bare digest.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    pub domain: Value,
+    pub types: BTreeMap<String, Vec<TypedDataField>>,
+    pub primary_type: String,
+    pub message: Value,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TypedDataField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub r#type: String,
+}
+
+impl TypedDataField {
+    fn new(name: &str, r#type: &str) -> TypedDataField {
+        TypedDataField {
+            name: name.to_string(),
+            r#type: r#type.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{b256, Address};
+
+    use super::*;
+    use crate::{
+        exchange::actions::{TokenDelegate, UsdSend},
+        helpers::Chain,
+        signature::agent::l1,
+    };
+
+    fn assert_typed_data_hash<T: Eip712>(payload: &T) {
+        let json = serde_json::to_value(payload.typed_data()).unwrap();
+        let typed_data: alloy_dyn_abi::TypedData = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            typed_data.eip712_signing_hash().unwrap(),
+            payload.eip712_signing_hash()
+        );
+    }
+
+    #[test]
+    fn test_typed_data_matches_signing_hash() {
+        let chain = Chain::Testnet;
+        assert_typed_data_hash(&l1::Agent {
+            source: chain.source().to_string(),
+            connectionId: b256!("de6c4037798a4434ca03cd05f00e3b803126221375cd1e7eaaaf041768be06eb"),
+        });
+        assert_typed_data_hash(&UsdSend {
+            signature_chain_id: chain.signature_chain_id(),
+            hyperliquid_chain: chain.hyperliquid_chain().to_string(),
+            destination: "0x0d1d9635d0640821d15e323ac8adadfa9c111414".to_string(),
+            amount: "1".to_string(),
+            time: 1690393044548,
+        });
+        assert_typed_data_hash(&TokenDelegate {
+            signature_chain_id: chain.signature_chain_id(),
+            hyperliquid_chain: chain.hyperliquid_chain().to_string(),
+            validator: Address::repeat_byte(7),
+            wei: 100_000_000,
+            is_undelegate: true,
+            nonce: 1690393044548,
+        });
+    }
 }
@@ -0,0 +1,176 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use utoipa::{
+    openapi::{schema::Type, ObjectBuilder, RefOr, Schema},
+    PartialSchema, ToSchema,
+};
+
+/// Defines an enum over the exchange's string values. Values this version does not know are
+/// kept in `Unknown` instead of failing to deserialize.
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident => $value:literal),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        #[serde(from = "String", into = "String")]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)+
+            /// A value this version of the crate does not know about.
+            Unknown(String),
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)+
+                    $name::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                match value.as_str() {
+                    $($value => $name::$variant,)+
+                    _ => $name::Unknown(value),
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                $name::from(value.to_string())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> String {
+                match value {
+                    $name::Unknown(value) => value,
+                    value => value.as_str().to_string(),
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl PartialSchema for $name {
+            fn schema() -> RefOr<Schema> {
+                ObjectBuilder::new()
+                    .schema_type(Type::String)
+                    .enum_values(Some([$($value),+]))
+                    .into()
+            }
+        }
+
+        impl ToSchema for $name {}
+    };
+}
+
+string_enum!(
+    /// Time in force of a limit order.
+    Tif {
+        /// Add liquidity only: canceled instead of crossing the book.
+        Alo => "Alo",
+        /// Immediate or cancel: the unfilled part is canceled.
+        Ioc => "Ioc",
+        /// Good til canceled.
+        Gtc => "Gtc",
+    }
+);
+
+string_enum!(
+    /// Whether a trigger order takes profit or stops a loss.
+    TpSl {
+        Tp => "tp",
+        Sl => "sl",
+    }
+);
+
+string_enum!(
+    /// Side of an order or fill.
+    Side {
+        Bid => "B",
+        Ask => "A",
+    }
+);
+
+impl Side {
+    pub fn from_is_buy(is_buy: bool) -> Side {
+        if is_buy {
+            Side::Bid
+        } else {
+            Side::Ask
+        }
+    }
+
+    pub fn is_buy(&self) -> bool {
+        *self == Side::Bid
+    }
+}
+
+string_enum!(
+    /// Status of an order as reported by order updates and order status queries.
+    OrderStatus {
+        Open => "open",
+        Filled => "filled",
+        Canceled => "canceled",
+        Triggered => "triggered",
+        Rejected => "rejected",
+        MarginCanceled => "marginCanceled",
+        VaultWithdrawalCanceled => "vaultWithdrawalCanceled",
+        OpenInterestCapCanceled => "openInterestCapCanceled",
+        SelfTradeCanceled => "selfTradeCanceled",
+        ReduceOnlyCanceled => "reduceOnlyCanceled",
+        SiblingFilledCanceled => "siblingFilledCanceled",
+        DelistedCanceled => "delistedCanceled",
+        LiquidatedCanceled => "liquidatedCanceled",
+        ScheduledCancel => "scheduledCancel",
+        TickRejected => "tickRejected",
+        MinTradeNtlRejected => "minTradeNtlRejected",
+        PerpMarginRejected => "perpMarginRejected",
+        ReduceOnlyRejected => "reduceOnlyRejected",
+        BadAloPxRejected => "badAloPxRejected",
+        IocCancelRejected => "iocCancelRejected",
+        BadTriggerPxRejected => "badTriggerPxRejected",
+        MarketOrderNoLiquidityRejected => "marketOrderNoLiquidityRejected",
+        PositionIncreaseAtOpenInterestCapRejected => "positionIncreaseAtOpenInterestCapRejected",
+        PositionFlipAtOpenInterestCapRejected => "positionFlipAtOpenInterestCapRejected",
+        TooAggressiveAtOpenInterestCapRejected => "tooAggressiveAtOpenInterestCapRejected",
+        OpenInterestIncreaseRejected => "openInterestIncreaseRejected",
+        InsufficientSpotBalanceRejected => "insufficientSpotBalanceRejected",
+        OracleRejected => "oracleRejected",
+        PerpMaxPositionRejected => "perpMaxPositionRejected",
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_enums_round_trip() {
+        assert_eq!(serde_json::to_string(&Tif::Ioc).unwrap(), r#""Ioc""#);
+        assert_eq!(serde_json::from_str::<Side>(r#""A""#).unwrap(), Side::Ask);
+        assert_eq!(
+            serde_json::from_str::<OrderStatus>(r#""marginCanceled""#).unwrap(),
+            OrderStatus::MarginCanceled
+        );
+
+        let status: OrderStatus = serde_json::from_str(r#""newlyAddedStatus""#).unwrap();
+        assert_eq!(status, OrderStatus::Unknown("newlyAddedStatus".to_string()));
+        assert_eq!(
+            serde_json::to_string(&status).unwrap(),
+            r#""newlyAddedStatus""#
+        );
+    }
+}
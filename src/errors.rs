@@ -1,3 +1,4 @@
+use alloy::primitives::Address;
 use thiserror::Error;
 
 #[derive(Error, Debug, Clone)]
@@ -55,10 +56,37 @@ pub enum Error {
     RmpParse(String),
     #[error("Invalid input number")]
     FloatStringParse,
+    #[error("Invalid cloid: {0}")]
+    InvalidCloid(String),
     #[error("No cloid found in order request when expected")]
     NoCloid,
     #[error("ECDSA signature failed: {0:?}")]
     SignatureFailure(String),
     #[error("Vault address not found")]
     VaultAddressNotFound,
+    #[error("Exchange error: {0}")]
+    Exchange(String),
+    #[error("Invalid order: {0}")]
+    InvalidOrder(String),
+    #[error("No open position in {0}")]
+    NoPosition(String),
+    #[error("Invalid leverage: {0}")]
+    InvalidLeverage(String),
+    #[error("Signature recovers to {recovered}, expected {expected}")]
+    SignerMismatch {
+        expected: Address,
+        recovered: Address,
+    },
+    #[error("Nonce error: {0}")]
+    Nonce(String),
+    #[error("Action signed for {found}, expected {expected}")]
+    ChainMismatch { expected: String, found: String },
+    #[error("Action signed with nonce {found}, submitted with {expected}")]
+    NonceMismatch { expected: u64, found: u64 },
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+    #[error("Invalid schedule: {0}")]
+    InvalidSchedule(String),
+    #[error("Agents can only be approved by the master account's signer")]
+    MasterSignerRequired,
 }
@@ -3,13 +3,18 @@ use alloy::{
     primitives::{keccak256, Address, B256},
     sol_types::{eip712_domain, SolValue},
 };
-use serde::{Deserialize, Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{json, Value};
 use utoipa::ToSchema;
 
 use super::{builder::BuilderInfo, cancel::CancelRequestCloid};
 use crate::{
     eip712::Eip712,
-    exchange::{cancel::CancelRequest, modify::ModifyRequest, order::OrderRequest},
+    exchange::{
+        cancel::CancelRequest,
+        modify::ModifyRequest,
+        order::{Grouping, OrderRequest},
+    },
 };
 
 fn eip_712_domain(chain_id: u64) -> Eip712Domain {
@@ -28,10 +33,29 @@ where
     s.serialize_str(&format!("0x{val:x}"))
 }
 
+fn deserialize_hex<'de, D>(d: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum HexOrNumber {
+        Hex(String),
+        Number(u64),
+    }
+
+    match HexOrNumber::deserialize(d)? {
+        HexOrNumber::Hex(hex) => {
+            u64::from_str_radix(hex.trim_start_matches("0x"), 16).map_err(serde::de::Error::custom)
+        }
+        HexOrNumber::Number(n) => Ok(n),
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UsdSend {
-    #[serde(serialize_with = "serialize_hex")]
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
     pub signature_chain_id: u64,
     pub hyperliquid_chain: String,
     pub destination: String,
@@ -46,14 +70,27 @@ impl Eip712 for UsdSend {
 
     fn struct_hash(&self) -> B256 {
         let items = (
-            keccak256("HyperliquidTransaction:UsdSend(string hyperliquidChain,string destination,string amount,uint64 time)"),
+            keccak256(self.encode_type()),
             keccak256(&self.hyperliquid_chain),
             keccak256(&self.destination),
             keccak256(&self.amount),
-            &self.time
+            &self.time,
         );
         keccak256(items.abi_encode())
     }
+
+    fn encode_type(&self) -> &'static str {
+        "HyperliquidTransaction:UsdSend(string hyperliquidChain,string destination,string amount,uint64 time)"
+    }
+
+    fn message(&self) -> Value {
+        json!({
+            "hyperliquidChain": self.hyperliquid_chain,
+            "destination": self.destination,
+            "amount": self.amount,
+            "time": self.time,
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
@@ -82,22 +119,66 @@ pub struct SetOracle {
 
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
-pub struct PerpDeploy {
-    pub set_oracle: SetOracle,
+pub struct RegisterAssetRequest {
+    pub coin: String,
+    pub sz_decimals: u32,
+    pub oracle_px: String,
+    pub margin_table_id: u32,
+    pub only_isolated: bool,
 }
 
+/// Settings of a new dex, given when registering its first asset.
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
-#[serde(tag = "type")]
-pub enum PerpDeployAction {
+pub struct PerpDexSchema {
+    pub full_name: String,
+    pub collateral_token: u32,
+    /// Lowercase address allowed to set oracle prices, the deployer if `None`.
+    pub oracle_updater: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterAsset {
+    pub max_gas: Option<u64>,
+    pub asset_request: RegisterAssetRequest,
+    pub dex: String,
+    pub schema: Option<PerpDexSchema>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HaltTrading {
+    pub coin: String,
+    pub is_halted: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SetFeeRecipient {
+    pub dex: String,
+    pub fee_recipient: String,
+}
+
+/// Actions of a builder-deployed perp dex (HIP-3). Per-coin lists are sorted by coin.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum PerpDeploy {
+    RegisterAsset(RegisterAsset),
     SetOracle(SetOracle),
+    /// `(coin, multiplier)` pairs.
+    SetFundingMultipliers(Vec<(String, String)>),
+    HaltTrading(HaltTrading),
+    /// `(coin, cap)` pairs, with the cap in USD.
+    SetOpenInterestCaps(Vec<(String, u64)>),
+    SetFeeRecipient(SetFeeRecipient),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BulkOrder {
     pub orders: Vec<OrderRequest>,
-    pub grouping: String,
+    pub grouping: Grouping,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub builder: Option<BuilderInfo>,
 }
@@ -123,10 +204,11 @@ pub struct BulkCancelCloid {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ApproveAgent {
-    #[serde(serialize_with = "serialize_hex")]
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
     pub signature_chain_id: u64,
     pub hyperliquid_chain: String,
     pub agent_address: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub agent_name: Option<String>,
     pub nonce: u64,
 }
@@ -138,20 +220,33 @@ impl Eip712 for ApproveAgent {
 
     fn struct_hash(&self) -> B256 {
         let items = (
-            keccak256("HyperliquidTransaction:ApproveAgent(string hyperliquidChain,address agentAddress,string agentName,uint64 nonce)"),
+            keccak256(self.encode_type()),
             keccak256(&self.hyperliquid_chain),
             &self.agent_address,
             keccak256(self.agent_name.as_deref().unwrap_or("")),
-            &self.nonce
+            &self.nonce,
         );
         keccak256(items.abi_encode())
     }
+
+    fn encode_type(&self) -> &'static str {
+        "HyperliquidTransaction:ApproveAgent(string hyperliquidChain,address agentAddress,string agentName,uint64 nonce)"
+    }
+
+    fn message(&self) -> Value {
+        json!({
+            "hyperliquidChain": self.hyperliquid_chain,
+            "agentAddress": self.agent_address,
+            "agentName": self.agent_name.as_deref().unwrap_or(""),
+            "nonce": self.nonce,
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Withdraw3 {
-    #[serde(serialize_with = "serialize_hex")]
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
     pub signature_chain_id: u64,
     pub hyperliquid_chain: String,
     pub destination: String,
@@ -166,7 +261,7 @@ impl Eip712 for Withdraw3 {
 
     fn struct_hash(&self) -> B256 {
         let items = (
-            keccak256("HyperliquidTransaction:Withdraw(string hyperliquidChain,string destination,string amount,uint64 time)"),
+            keccak256(self.encode_type()),
             keccak256(&self.hyperliquid_chain),
             keccak256(&self.destination),
             keccak256(&self.amount),
@@ -174,12 +269,25 @@ impl Eip712 for Withdraw3 {
         );
         keccak256(items.abi_encode())
     }
+
+    fn encode_type(&self) -> &'static str {
+        "HyperliquidTransaction:Withdraw(string hyperliquidChain,string destination,string amount,uint64 time)"
+    }
+
+    fn message(&self) -> Value {
+        json!({
+            "hyperliquidChain": self.hyperliquid_chain,
+            "destination": self.destination,
+            "amount": self.amount,
+            "time": self.time,
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SpotSend {
-    #[serde(serialize_with = "serialize_hex")]
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
     pub signature_chain_id: u64,
     pub hyperliquid_chain: String,
     pub destination: String,
@@ -195,7 +303,7 @@ impl Eip712 for SpotSend {
 
     fn struct_hash(&self) -> B256 {
         let items = (
-            keccak256("HyperliquidTransaction:SpotSend(string hyperliquidChain,string destination,string token,string amount,uint64 time)"),
+            keccak256(self.encode_type()),
             keccak256(&self.hyperliquid_chain),
             keccak256(&self.destination),
             keccak256(&self.token),
@@ -204,6 +312,20 @@ impl Eip712 for SpotSend {
         );
         keccak256(items.abi_encode())
     }
+
+    fn encode_type(&self) -> &'static str {
+        "HyperliquidTransaction:SpotSend(string hyperliquidChain,string destination,string token,string amount,uint64 time)"
+    }
+
+    fn message(&self) -> Value {
+        json!({
+            "hyperliquidChain": self.hyperliquid_chain,
+            "destination": self.destination,
+            "token": self.token,
+            "amount": self.amount,
+            "time": self.time,
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -215,11 +337,12 @@ pub struct SpotUser {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ClassTransfer {
-    pub usdc: u64,
-    pub to_perp: bool,
-    #[serde(serialize_with = "serialize_hex")]
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
     pub signature_chain_id: u64,
     pub hyperliquid_chain: String,
+    pub amount: String,
+    pub to_perp: bool,
+    pub nonce: u64,
 }
 
 impl Eip712 for ClassTransfer {
@@ -229,19 +352,33 @@ impl Eip712 for ClassTransfer {
 
     fn struct_hash(&self) -> B256 {
         let items = (
-            keccak256("HyperliquidTransaction:ClassTransfer(string hyperliquidChain,uint64 usdc,bool toPerp)"),
+            keccak256(self.encode_type()),
             keccak256(&self.hyperliquid_chain),
-            &self.usdc,
+            keccak256(&self.amount),
             self.to_perp,
+            &self.nonce,
         );
         keccak256(items.abi_encode())
     }
+
+    fn encode_type(&self) -> &'static str {
+        "HyperliquidTransaction:UsdClassTransfer(string hyperliquidChain,string amount,bool toPerp,uint64 nonce)"
+    }
+
+    fn message(&self) -> Value {
+        json!({
+            "hyperliquidChain": self.hyperliquid_chain,
+            "amount": self.amount,
+            "toPerp": self.to_perp,
+            "nonce": self.nonce,
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SendAsset {
-    #[serde(serialize_with = "serialize_hex")]
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
     pub signature_chain_id: u64,
     pub hyperliquid_chain: String,
     pub destination: String,
@@ -260,7 +397,7 @@ impl Eip712 for SendAsset {
 
     fn struct_hash(&self) -> B256 {
         let items = (
-            keccak256("HyperliquidTransaction:SendAsset(string hyperliquidChain,string destination,string sourceDex,string destinationDex,string token,string amount,string fromSubAccount,uint64 nonce)"),
+            keccak256(self.encode_type()),
             keccak256(&self.hyperliquid_chain),
             keccak256(&self.destination),
             keccak256(&self.source_dex),
@@ -272,16 +409,200 @@ impl Eip712 for SendAsset {
         );
         keccak256(items.abi_encode())
     }
+
+    fn encode_type(&self) -> &'static str {
+        "HyperliquidTransaction:SendAsset(string hyperliquidChain,string destination,string sourceDex,string destinationDex,string token,string amount,string fromSubAccount,uint64 nonce)"
+    }
+
+    fn message(&self) -> Value {
+        json!({
+            "hyperliquidChain": self.hyperliquid_chain,
+            "destination": self.destination,
+            "sourceDex": self.source_dex,
+            "destinationDex": self.destination_dex,
+            "token": self.token,
+            "amount": self.amount,
+            "fromSubAccount": self.from_sub_account,
+            "nonce": self.nonce,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CDeposit {
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
+    pub signature_chain_id: u64,
+    pub hyperliquid_chain: String,
+    pub wei: u64,
+    pub nonce: u64,
+}
+
+impl Eip712 for CDeposit {
+    fn domain(&self) -> Eip712Domain {
+        eip_712_domain(self.signature_chain_id)
+    }
+
+    fn struct_hash(&self) -> B256 {
+        let items = (
+            keccak256(self.encode_type()),
+            keccak256(&self.hyperliquid_chain),
+            &self.wei,
+            &self.nonce,
+        );
+        keccak256(items.abi_encode())
+    }
+
+    fn encode_type(&self) -> &'static str {
+        "HyperliquidTransaction:CDeposit(string hyperliquidChain,uint64 wei,uint64 nonce)"
+    }
+
+    fn message(&self) -> Value {
+        json!({
+            "hyperliquidChain": self.hyperliquid_chain,
+            "wei": self.wei,
+            "nonce": self.nonce,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CWithdraw {
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
+    pub signature_chain_id: u64,
+    pub hyperliquid_chain: String,
+    pub wei: u64,
+    pub nonce: u64,
+}
+
+impl Eip712 for CWithdraw {
+    fn domain(&self) -> Eip712Domain {
+        eip_712_domain(self.signature_chain_id)
+    }
+
+    fn struct_hash(&self) -> B256 {
+        let items = (
+            keccak256(self.encode_type()),
+            keccak256(&self.hyperliquid_chain),
+            &self.wei,
+            &self.nonce,
+        );
+        keccak256(items.abi_encode())
+    }
+
+    fn encode_type(&self) -> &'static str {
+        "HyperliquidTransaction:CWithdraw(string hyperliquidChain,uint64 wei,uint64 nonce)"
+    }
+
+    fn message(&self) -> Value {
+        json!({
+            "hyperliquidChain": self.hyperliquid_chain,
+            "wei": self.wei,
+            "nonce": self.nonce,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenDelegate {
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
+    pub signature_chain_id: u64,
+    pub hyperliquid_chain: String,
+    pub validator: Address,
+    pub wei: u64,
+    pub is_undelegate: bool,
+    pub nonce: u64,
+}
+
+impl Eip712 for TokenDelegate {
+    fn domain(&self) -> Eip712Domain {
+        eip_712_domain(self.signature_chain_id)
+    }
+
+    fn struct_hash(&self) -> B256 {
+        let items = (
+            keccak256(self.encode_type()),
+            keccak256(&self.hyperliquid_chain),
+            &self.validator,
+            &self.wei,
+            self.is_undelegate,
+            &self.nonce,
+        );
+        keccak256(items.abi_encode())
+    }
+
+    fn encode_type(&self) -> &'static str {
+        "HyperliquidTransaction:TokenDelegate(string hyperliquidChain,address validator,uint64 wei,bool isUndelegate,uint64 nonce)"
+    }
+
+    fn message(&self) -> Value {
+        json!({
+            "hyperliquidChain": self.hyperliquid_chain,
+            "validator": self.validator,
+            "wei": self.wei,
+            "isUndelegate": self.is_undelegate,
+            "nonce": self.nonce,
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct VaultTransfer {
-    pub vault_address: Address,
+    pub vault_address: String,
+    pub is_deposit: bool,
+    pub usd: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateVault {
+    pub name: String,
+    pub description: String,
+    pub initial_usd: u64,
+    pub nonce: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultModify {
+    pub vault_address: String,
+    pub allow_deposits: Option<bool>,
+    pub always_close_on_withdraw: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultDistribute {
+    pub vault_address: String,
+    pub usd: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSubAccount {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountTransfer {
+    pub sub_account_user: String,
     pub is_deposit: bool,
     pub usd: u64,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountSpotTransfer {
+    pub sub_account_user: String,
+    pub is_deposit: bool,
+    pub token: String,
+    pub amount: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SetReferrer {
@@ -297,7 +618,7 @@ pub struct EvmUserModify {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ApproveBuilderFee {
-    #[serde(serialize_with = "serialize_hex")]
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
     pub signature_chain_id: u64,
     pub hyperliquid_chain: String,
     pub builder: Address,
@@ -305,6 +626,38 @@ pub struct ApproveBuilderFee {
     pub nonce: u64,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapRequest {
+    #[serde(rename = "a", alias = "asset")]
+    pub asset: u32,
+    #[serde(rename = "b", alias = "isBuy")]
+    pub is_buy: bool,
+    #[serde(rename = "s", alias = "sz")]
+    pub sz: String,
+    #[serde(rename = "r", alias = "reduceOnly")]
+    pub reduce_only: bool,
+    #[serde(rename = "m", alias = "minutes")]
+    pub minutes: u32,
+    #[serde(rename = "t", alias = "randomize")]
+    pub randomize: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapOrder {
+    pub twap: TwapRequest,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapCancel {
+    #[serde(rename = "a", alias = "asset")]
+    pub asset: u32,
+    #[serde(rename = "t", alias = "twapId")]
+    pub twap_id: u64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ScheduleCancel {
@@ -319,7 +672,7 @@ impl Eip712 for ApproveBuilderFee {
 
     fn struct_hash(&self) -> B256 {
         let items = (
-            keccak256("HyperliquidTransaction:ApproveBuilderFee(string hyperliquidChain,string maxFeeRate,address builder,uint64 nonce)"),
+            keccak256(self.encode_type()),
             keccak256(&self.hyperliquid_chain),
             keccak256(&self.max_fee_rate),
             &self.builder,
@@ -327,4 +680,17 @@ impl Eip712 for ApproveBuilderFee {
         );
         keccak256(items.abi_encode())
     }
+
+    fn encode_type(&self) -> &'static str {
+        "HyperliquidTransaction:ApproveBuilderFee(string hyperliquidChain,string maxFeeRate,address builder,uint64 nonce)"
+    }
+
+    fn message(&self) -> Value {
+        json!({
+            "hyperliquidChain": self.hyperliquid_chain,
+            "maxFeeRate": self.max_fee_rate,
+            "builder": self.builder,
+            "nonce": self.nonce,
+        })
+    }
 }
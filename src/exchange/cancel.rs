@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
-use uuid::Uuid;
+
+use crate::cloid::Cloid;
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct ClientCancelRequest {
@@ -19,11 +20,11 @@ pub struct CancelRequest {
 #[derive(Debug, ToSchema)]
 pub struct ClientCancelRequestCloid {
     pub asset: u32,
-    pub cloid: Uuid,
+    pub cloid: Cloid,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct CancelRequestCloid {
     pub asset: u32,
-    pub cloid: String,
+    pub cloid: Cloid,
 }
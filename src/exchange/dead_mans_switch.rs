@@ -0,0 +1,105 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+    time::{interval, MissedTickBehavior},
+};
+
+use crate::{errors::Error, prelude::*, signature::signer::HyperliquidSigner, ExchangeClient};
+
+/// The exchange rejects cancels scheduled less than this far in the future.
+const MIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Keeps a scheduled cancel of all open orders pushed into the future while the process is
+/// healthy. If the process dies or loses connectivity, the heartbeats stop and the exchange
+/// cancels the orders once the last scheduled time passes.
+#[derive(Debug)]
+pub struct DeadMansSwitch {
+    stop: oneshot::Sender<()>,
+    handle: JoinHandle<Result<()>>,
+}
+
+impl DeadMansSwitch {
+    /// Every `heartbeat`, schedules the cancel `timeout` from now. `timeout` has to be at
+    /// least 5 seconds and longer than a non-zero `heartbeat`, so one late heartbeat does not
+    /// trip it. Failed heartbeats are sent to the returned receiver and retried on the next
+    /// tick.
+    ///
+    /// Dropping the switch stops the heartbeats but leaves the last cancel scheduled.
+    pub fn start<S: HyperliquidSigner + 'static>(
+        client: Arc<ExchangeClient<S>>,
+        timeout: Duration,
+        heartbeat: Duration,
+    ) -> Result<(DeadMansSwitch, mpsc::UnboundedReceiver<Error>)> {
+        if heartbeat.is_zero() {
+            return Err(Error::InvalidSchedule(
+                "heartbeat must be longer than zero".to_string(),
+            ));
+        }
+        if timeout < MIN_TIMEOUT || timeout <= heartbeat {
+            return Err(Error::InvalidSchedule(format!(
+                "timeout {timeout:?} must be at least {MIN_TIMEOUT:?} and longer than the heartbeat {heartbeat:?}"
+            )));
+        }
+
+        let (stop, mut stopped) = oneshot::channel();
+        let (failures, failure_receiver) = mpsc::unbounded_channel();
+
+        let handle = tokio::spawn(async move {
+            let mut ticker = interval(heartbeat);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    stop = &mut stopped => {
+                        // Only an explicit stop clears the schedule, not a dropped switch.
+                        if stop.is_ok() {
+                            client.schedule_cancel(None).await?.into_result()?;
+                        }
+                        return Ok(());
+                    }
+                    _ = ticker.tick() => {
+                        let time = client.nonces.now() + timeout.as_millis() as u64;
+                        let result = client
+                            .schedule_cancel(Some(time))
+                            .await
+                            .and_then(|response| response.into_result());
+                        if let Err(err) = result {
+                            let _ = failures.send(err);
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok((DeadMansSwitch { stop, handle }, failure_receiver))
+    }
+
+    /// Stops the heartbeats and removes the scheduled cancel, leaving open orders in place.
+    pub async fn stop(self) -> Result<()> {
+        let _ = self.stop.send(());
+        self.handle
+            .await
+            .map_err(|e| Error::GenericRequest(e.to_string()))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::client;
+
+    #[test]
+    fn test_invalid_timing_is_rejected() {
+        let client = Arc::new(client("http://127.0.0.1:0"));
+
+        for (timeout, heartbeat) in [(30, 0), (4, 1), (10, 10)] {
+            let result = DeadMansSwitch::start(
+                Arc::clone(&client),
+                Duration::from_secs(timeout),
+                Duration::from_secs(heartbeat),
+            );
+            assert!(matches!(result, Err(Error::InvalidSchedule(_))));
+        }
+    }
+}
@@ -1,14 +1,21 @@
-use alloy::primitives::B256;
+use alloy::primitives::{Address, B256};
 use serde::{Deserialize, Serialize};
 
-use crate::exchange::hash_generator::Actions;
-
+use crate::{eip712::TypedData, exchange::hash_generator::Actions};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MessageResponse {
     pub action: Actions,
     pub message: B256,
     pub nonce: u64,
+    /// Vault the action was hashed for; it has to be sent along with the signature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vault_address: Option<Address>,
+    /// Expiry the action was hashed with; it has to be sent along with the signature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_after: Option<u64>,
+    /// `eth_signTypedData_v4` document of `message`, for wallets that sign typed data.
+    pub typed_data: TypedData,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, utoipa::ToSchema)]
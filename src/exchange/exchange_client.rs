@@ -1,75 +1,108 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
-use alloy::{
-    primitives::{Address, Signature},
-    signers::local::PrivateKeySigner,
-};
-use log::debug;
+use alloy::{primitives::Address, signers::local::PrivateKeySigner};
 use reqwest::Client;
-use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 
 use crate::{
-    exchange::{actions::*, hash_generator::Actions},
-    helpers::{next_nonce, BaseUrl},
+    assets::{AssetInfo, AssetResolver},
+    decimal::{Px, Sz},
+    eip712::Eip712,
+    enums::{Tif, TpSl},
+    exchange::{
+        actions::*,
+        builder::BuilderInfo,
+        cancel::{
+            CancelRequest, CancelRequestCloid, ClientCancelRequest, ClientCancelRequestCloid,
+        },
+        hash_generator::Actions,
+        modify::{ClientModifyRequest, ModifyRequest},
+        order::{BracketOrder, ClientLimit, ClientOrder, ClientOrderRequest, Grouping},
+        signed_action::{post_signed, SignedAction},
+    },
+    helpers::{now_timestamp_ms, BaseUrl, Chain},
     info::InfoClient,
     meta::Meta,
+    nonce::NonceManager,
     prelude::*,
     req::HttpClient,
-    signature::create_signature::sign_l1_action,
-    Error, ExchangeResponseStatus,
+    rules::Rounding,
+    signature::{
+        create_signature::{sign_l1_action, sign_typed_data},
+        signer::HyperliquidSigner,
+    },
+    Error, ExchangeResponse,
 };
 
 #[derive(Debug)]
-pub struct ExchangeClient {
+pub struct ExchangeClient<S: HyperliquidSigner = PrivateKeySigner> {
     pub http_client: HttpClient,
-    pub wallet: PrivateKeySigner,
+    pub wallet: S,
     pub meta: Meta,
     pub vault_address: Option<Address>,
-    pub coin_to_asset: HashMap<String, u32>,
+    pub assets: Arc<AssetResolver>,
+    pub chain: Chain,
+    /// Master account an agent wallet trades for. `None` when `wallet` is the account itself.
+    pub account_address: Option<Address>,
+    /// Nonces follow the local clock unless [`NonceManager::set_server_time`] is called.
+    pub nonces: Arc<NonceManager>,
+    /// Time in milliseconds after which L1 actions sent by this client are rejected.
+    pub expires_after: Option<u64>,
 }
 
-fn serialize_sig<S>(sig: &Signature, s: S) -> std::result::Result<S::Ok, S::Error>
-where
-    S: Serializer,
-{
-    let mut state = s.serialize_struct("Signature", 3)?;
-    state.serialize_field("r", &sig.r())?;
-    state.serialize_field("s", &sig.s())?;
-    state.serialize_field("v", &(27 + sig.v() as u64))?;
-    state.end()
+/// Converts a USD amount to the micro-USD integer that USD transfer actions carry.
+fn usd_to_micros(usd: Decimal) -> Result<u64> {
+    if usd.is_sign_negative() {
+        return Err(Error::InvalidAmount(format!("{usd} is negative")));
+    }
+    usd.checked_mul(Decimal::from(1_000_000))
+        .and_then(|micros| micros.round().to_u64())
+        .ok_or_else(|| Error::InvalidAmount(format!("{usd} is too large")))
 }
 
-#[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct ExchangePayload {
-    action: serde_json::Value,
-    #[serde(serialize_with = "serialize_sig")]
-    signature: Signature,
-    nonce: u64,
-    vault_address: Option<Address>,
+/// Converts a USD amount that may be negative, such as a margin removal, to micro-USD.
+pub(crate) fn signed_usd_to_micros(usd: Decimal) -> Result<i64> {
+    usd.checked_mul(Decimal::from(1_000_000))
+        .and_then(|micros| micros.round().to_i64())
+        .ok_or_else(|| Error::InvalidAmount(format!("{usd} is too large")))
+}
+
+fn market_order(
+    asset: u32,
+    is_buy: bool,
+    sz: Sz,
+    limit_px: Px,
+    reduce_only: bool,
+) -> ClientOrderRequest {
+    ClientOrderRequest {
+        asset,
+        is_buy,
+        reduce_only,
+        limit_px,
+        sz,
+        cloid: None,
+        order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Ioc }),
+    }
 }
 
-impl ExchangeClient {
+impl<S: HyperliquidSigner> ExchangeClient<S> {
     pub async fn new(
         info_client: Option<InfoClient>,
-        wallet: PrivateKeySigner,
+        wallet: S,
         base_url: Option<BaseUrl>,
         vault_address: Option<Address>,
         client: Option<Client>,
-    ) -> Result<ExchangeClient> {
-        let base_url = base_url.unwrap_or(BaseUrl::Mainnet);
+    ) -> Result<ExchangeClient<S>> {
+        let base_url = base_url.unwrap_or(Chain::default().base_url());
         let client = client.unwrap_or_default();
         let info_client = match info_client {
             Some(client) => client,
-            None => InfoClient::new(None, Some(base_url.clone())).await?,
+            None => InfoClient::new(None, Some(base_url)).await?,
         };
         let meta = info_client.meta().await?;
-        let coin_to_asset = meta
-            .universe
-            .iter()
-            .enumerate()
-            .map(|(i, asset_info)| (asset_info.name.clone(), i as u32))
-            .collect();
+        let spot_meta = info_client.spot_meta().await?;
+        let mut assets = AssetResolver::new(&meta, Some(&spot_meta));
+        info_client.add_perp_dexs(&mut assets).await?;
 
         Ok(ExchangeClient {
             wallet,
@@ -79,33 +112,439 @@ impl ExchangeClient {
                 client,
                 base_url: base_url.get_url(),
             },
-            coin_to_asset,
+            assets: Arc::new(assets),
+            chain: base_url.chain(),
+            account_address: None,
+            nonces: NonceManager::global(),
+            expires_after: None,
         })
     }
 
-    async fn post(
+    /// A client that signs with `agent` on behalf of this client's account (or vault). The
+    /// agent has to be approved first, see [`ExchangeClient::approve_agent`].
+    pub fn with_agent<A: HyperliquidSigner>(&self, agent: A) -> ExchangeClient<A> {
+        ExchangeClient {
+            http_client: self.http_client.clone(),
+            wallet: agent,
+            meta: self.meta.clone(),
+            vault_address: self.vault_address,
+            assets: Arc::clone(&self.assets),
+            chain: self.chain,
+            account_address: Some(self.account_address.unwrap_or(self.wallet.address())),
+            nonces: Arc::clone(&self.nonces),
+            expires_after: self.expires_after,
+        }
+    }
+
+    /// Takes nonces from `nonces` instead of the process-wide [`NonceManager::global`], e.g.
+    /// one that persists them to disk.
+    pub fn with_nonce_manager(mut self, nonces: Arc<NonceManager>) -> Self {
+        self.nonces = nonces;
+        self
+    }
+
+    /// Makes the exchange reject L1 actions (orders, cancels, leverage updates, ...) that
+    /// arrive after `expires_after`, a timestamp in milliseconds. `None` disables the expiry.
+    pub fn set_expires_after(&mut self, expires_after: Option<u64>) {
+        self.expires_after = expires_after;
+    }
+
+    fn next_nonce(&self) -> Result<u64> {
+        self.nonces.next(self.wallet.address())
+    }
+
+    /// The address whose state this client trades: the vault, the master account of an agent,
+    /// or the wallet itself.
+    pub fn user_address(&self) -> Address {
+        self.vault_address
+            .or(self.account_address)
+            .unwrap_or(self.wallet.address())
+    }
+
+    fn info_client(&self) -> InfoClient {
+        InfoClient::from_http_client(self.http_client.clone())
+    }
+
+    async fn post_l1_action(&self, action: Actions) -> Result<ExchangeResponse> {
+        let timestamp = self.next_nonce()?;
+        self.post_l1_action_with_nonce(action, timestamp).await
+    }
+
+    /// Like [`Self::post_l1_action`], for actions that embed the nonce they are signed with.
+    async fn post_l1_action_with_nonce(
+        &self,
+        action: Actions,
+        timestamp: u64,
+    ) -> Result<ExchangeResponse> {
+        let connection_id = action.hash(timestamp, self.vault_address, self.expires_after)?;
+        let signature = sign_l1_action(&self.wallet, connection_id, self.chain).await?;
+
+        let signed = SignedAction {
+            expires_after: self.expires_after,
+            ..SignedAction::new(action, signature, timestamp, self.vault_address)
+        };
+        post_signed(&self.http_client, &signed).await
+    }
+
+    /// Signs `payload` as EIP-712 typed data and posts `action`. User-signed actions are never
+    /// sent on behalf of a vault.
+    async fn post_user_action<T: Eip712>(
         &self,
-        action: serde_json::Value,
-        signature: Signature,
+        payload: &T,
+        action: Actions,
         nonce: u64,
-    ) -> Result<ExchangeResponseStatus> {
-        let exchange_payload = ExchangePayload {
-            action,
-            signature,
-            nonce,
-            vault_address: self.vault_address,
+    ) -> Result<ExchangeResponse> {
+        let signature = sign_typed_data(payload, &self.wallet).await?;
+
+        let signed = SignedAction::new(action, signature, nonce, None);
+        post_signed(&self.http_client, &signed).await
+    }
+
+    /// Looks up the asset index of a coin, e.g. `"ETH"`, `"PURR/USDC"` or `"@107"`.
+    pub fn asset(&self, coin: &str) -> Result<u32> {
+        self.assets.asset(coin)
+    }
+
+    pub async fn order(&self, order: ClientOrderRequest) -> Result<ExchangeResponse> {
+        self.bulk_order(vec![order]).await
+    }
+
+    pub async fn order_with_builder(
+        &self,
+        order: ClientOrderRequest,
+        builder: BuilderInfo,
+    ) -> Result<ExchangeResponse> {
+        self.bulk_order_with_builder(vec![order], builder).await
+    }
+
+    pub async fn bulk_order(&self, orders: Vec<ClientOrderRequest>) -> Result<ExchangeResponse> {
+        self.bulk_order_internal(orders, Grouping::Na, None).await
+    }
+
+    /// Places the entry order together with its take-profit and stop-loss triggers, which
+    /// only become active once the entry fills.
+    pub async fn bracket_order(&self, bracket: BracketOrder) -> Result<ExchangeResponse> {
+        self.bulk_order_internal(bracket.into_orders(), Grouping::NormalTpsl, None)
+            .await
+    }
+
+    /// Attaches take-profit and/or stop-loss triggers to the open position in `coin`. They
+    /// close the whole position and follow it as it is resized.
+    pub async fn position_tpsl(
+        &self,
+        coin: &str,
+        take_profit: Option<Px>,
+        stop_loss: Option<Px>,
+    ) -> Result<ExchangeResponse> {
+        if take_profit.is_none() && stop_loss.is_none() {
+            return Err(Error::InvalidOrder(
+                "position TP/SL needs a take-profit or a stop-loss price".to_string(),
+            ));
+        }
+        let info = self.assets.info(coin).ok_or(Error::AssetNotFound)?;
+        let position = self.position_size(info).await?;
+        let (is_buy, sz) = (position < Sz::ZERO, Sz::new(position.value().abs()));
+
+        let mut orders = Vec::new();
+        if let Some(trigger_px) = take_profit {
+            orders.push(ClientOrderRequest::tpsl(
+                info.asset,
+                is_buy,
+                sz,
+                trigger_px,
+                TpSl::Tp,
+            ));
+        }
+        if let Some(trigger_px) = stop_loss {
+            orders.push(ClientOrderRequest::tpsl(
+                info.asset,
+                is_buy,
+                sz,
+                trigger_px,
+                TpSl::Sl,
+            ));
+        }
+        self.bulk_order_internal(orders, Grouping::PositionTpsl, None)
+            .await
+    }
+
+    pub async fn bulk_order_with_builder(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        mut builder: BuilderInfo,
+    ) -> Result<ExchangeResponse> {
+        builder.builder = builder.builder.to_lowercase();
+        self.bulk_order_internal(orders, Grouping::Na, Some(builder))
+            .await
+    }
+
+    async fn bulk_order_internal(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        grouping: Grouping,
+        builder: Option<BuilderInfo>,
+    ) -> Result<ExchangeResponse> {
+        let mut transformed_orders = Vec::new();
+        for order in orders {
+            self.assets.validate(&order)?;
+            transformed_orders.push(order.convert()?);
+        }
+
+        let cloids = transformed_orders.iter().map(|order| order.cloid).collect();
+        let action = Actions::Order(BulkOrder {
+            orders: transformed_orders,
+            grouping,
+            builder,
+        });
+        let mut response = self.post_l1_action(action).await?;
+        response.link_cloids(cloids);
+        Ok(response)
+    }
+
+    /// Opens a position with an IOC order priced `slippage` (e.g. `0.05` for 5%) through the
+    /// current mid.
+    pub async fn market_open(
+        &self,
+        coin: &str,
+        is_buy: bool,
+        sz: Sz,
+        slippage: f64,
+    ) -> Result<ExchangeResponse> {
+        let info = self.assets.info(coin).ok_or(Error::AssetNotFound)?;
+        let limit_px = self.slippage_price(info, is_buy, slippage).await?;
+        self.order(market_order(info.asset, is_buy, sz, limit_px, false))
+            .await
+    }
+
+    /// Closes `sz` of the position in `coin`, or all of it when `sz` is `None`, with a reduce-only
+    /// IOC order priced `slippage` through the current mid.
+    pub async fn market_close(
+        &self,
+        coin: &str,
+        sz: Option<Sz>,
+        slippage: f64,
+    ) -> Result<ExchangeResponse> {
+        let info = self.assets.info(coin).ok_or(Error::AssetNotFound)?;
+        let position = self.position_size(info).await?;
+
+        let is_buy = position < Sz::ZERO;
+        let sz = sz.unwrap_or(Sz::new(position.value().abs()));
+        let limit_px = self.slippage_price(info, is_buy, slippage).await?;
+        self.order(market_order(info.asset, is_buy, sz, limit_px, true))
+            .await
+    }
+
+    /// Signed size of the open position in the asset, failing when there is none.
+    async fn position_size(&self, info: &AssetInfo) -> Result<Sz> {
+        let info_client = self.info_client();
+        let user_state = match &info.dex {
+            Some(dex) => {
+                info_client
+                    .user_state_for_dex(self.user_address(), dex)
+                    .await?
+            }
+            None => info_client.user_state(self.user_address()).await?,
         };
-        let res = serde_json::to_string(&exchange_payload)
-            .map_err(|e| Error::JsonParse(e.to_string()))?;
-        debug!("Sending request {res:?}");
+        user_state
+            .asset_positions
+            .iter()
+            .find(|asset_position| asset_position.position.coin == info.name)
+            .map(|asset_position| asset_position.position.size())
+            .transpose()?
+            .filter(|szi| *szi != Sz::ZERO)
+            .ok_or_else(|| Error::NoPosition(info.name.clone()))
+    }
 
-        let output = &self
-            .http_client
-            .post("/exchange", res)
+    async fn slippage_price(&self, info: &AssetInfo, is_buy: bool, slippage: f64) -> Result<Px> {
+        let info_client = self.info_client();
+        let mids = match &info.dex {
+            Some(dex) => info_client.all_mids_for_dex(dex).await?,
+            None => info_client.all_mids().await?,
+        };
+        let mid: Px = mids.get(&info.name).ok_or(Error::AssetNotFound)?.parse()?;
+        let slippage = Px::try_from(slippage)?.value();
+        let px = if is_buy {
+            mid.value() * (Decimal::ONE + slippage)
+        } else {
+            mid.value() * (Decimal::ONE - slippage)
+        };
+        Ok(info.rule().round_px(Px::new(px), Rounding::Nearest))
+    }
+
+    /// Sets the leverage of `coin`, checked against the asset's maximum leverage and whether it
+    /// only supports isolated margin.
+    pub async fn update_leverage(
+        &self,
+        coin: &str,
+        leverage: u32,
+        is_cross: bool,
+    ) -> Result<ExchangeResponse> {
+        let info = self.perp_info(coin)?;
+        let max_leverage = info.max_leverage.unwrap_or_default();
+        if leverage == 0 || leverage > max_leverage {
+            return Err(Error::InvalidLeverage(format!(
+                "{coin} supports leverage between 1 and {max_leverage}, got {leverage}"
+            )));
+        }
+        if is_cross && info.only_isolated {
+            return Err(Error::InvalidLeverage(format!(
+                "{coin} only supports isolated margin"
+            )));
+        }
+
+        let action = Actions::UpdateLeverage(UpdateLeverage {
+            asset: info.asset,
+            is_cross,
+            leverage,
+        });
+        self.post_l1_action(action).await
+    }
+
+    /// Adds `usd` of margin to the isolated position in `coin`, or removes it when negative.
+    pub async fn update_isolated_margin(
+        &self,
+        coin: &str,
+        usd: Decimal,
+    ) -> Result<ExchangeResponse> {
+        let info = self.perp_info(coin)?;
+        // payload expects usd without decimals
+        let ntli = signed_usd_to_micros(usd)?;
+
+        let action = Actions::UpdateIsolatedMargin(UpdateIsolatedMargin {
+            asset: info.asset,
+            is_buy: true,
+            ntli,
+        });
+        self.post_l1_action(action).await
+    }
+
+    fn perp_info(&self, coin: &str) -> Result<&AssetInfo> {
+        let info = self.assets.info(coin).ok_or(Error::AssetNotFound)?;
+        if info.is_spot {
+            return Err(Error::InvalidLeverage(format!("{coin} is not a perp")));
+        }
+        Ok(info)
+    }
+
+    pub async fn cancel(&self, cancel: ClientCancelRequest) -> Result<ExchangeResponse> {
+        self.bulk_cancel(vec![cancel]).await
+    }
+
+    pub async fn bulk_cancel(&self, cancels: Vec<ClientCancelRequest>) -> Result<ExchangeResponse> {
+        let cancels = cancels
+            .into_iter()
+            .map(|cancel| CancelRequest {
+                asset: cancel.asset,
+                oid: cancel.oid,
+            })
+            .collect();
+
+        let action = Actions::Cancel(BulkCancel { cancels });
+        self.post_l1_action(action).await
+    }
+
+    pub async fn cancel_by_cloid(
+        &self,
+        cancel: ClientCancelRequestCloid,
+    ) -> Result<ExchangeResponse> {
+        self.bulk_cancel_by_cloid(vec![cancel]).await
+    }
+
+    pub async fn bulk_cancel_by_cloid(
+        &self,
+        cancels: Vec<ClientCancelRequestCloid>,
+    ) -> Result<ExchangeResponse> {
+        let cancels = cancels
+            .into_iter()
+            .map(|cancel| CancelRequestCloid {
+                asset: cancel.asset,
+                cloid: cancel.cloid,
+            })
+            .collect();
+
+        let action = Actions::CancelByCloid(BulkCancelCloid { cancels });
+        self.post_l1_action(action).await
+    }
+
+    pub async fn modify(&self, modify: ClientModifyRequest) -> Result<ExchangeResponse> {
+        self.bulk_modify(vec![modify]).await
+    }
+
+    pub async fn bulk_modify(
+        &self,
+        modifies: Vec<ClientModifyRequest>,
+    ) -> Result<ExchangeResponse> {
+        let mut transformed_modifies = Vec::new();
+        for modify in modifies {
+            self.assets.validate(&modify.order)?;
+            transformed_modifies.push(ModifyRequest {
+                oid: modify.oid,
+                order: modify.order.convert()?,
+            });
+        }
+
+        let cloids = transformed_modifies
+            .iter()
+            .map(|modify| modify.order.cloid)
+            .collect();
+        let action = Actions::BatchModify(BulkModify {
+            modifies: transformed_modifies,
+        });
+        let mut response = self.post_l1_action(action).await?;
+        response.link_cloids(cloids);
+        Ok(response)
+    }
+
+    /// Starts an exchange-side TWAP that trades `sz` of `coin` in slices over `minutes`
+    /// (5 to 1440). `sz` has to be a positive multiple of the asset's lot size. With
+    /// `randomize` the slice sizes and timing vary. The response data is
+    /// [`ExchangeResponseData::TwapOrder`](crate::ExchangeResponseData::TwapOrder) with the
+    /// TWAP's id.
+    pub async fn twap_order(
+        &self,
+        coin: &str,
+        is_buy: bool,
+        sz: Sz,
+        minutes: u32,
+        randomize: bool,
+        reduce_only: bool,
+    ) -> Result<ExchangeResponse> {
+        let info = self.assets.info(coin).ok_or(Error::AssetNotFound)?;
+        info.rule().validate_sz(sz)?;
+        if sz <= Sz::ZERO {
+            return Err(Error::InvalidOrder(format!(
+                "TWAP size {sz} has to be positive"
+            )));
+        }
+        let action = Actions::TwapOrder(TwapOrder {
+            twap: TwapRequest {
+                asset: info.asset,
+                is_buy,
+                sz: sz.to_wire(),
+                reduce_only,
+                minutes,
+                randomize,
+            },
+        });
+        self.post_l1_action(action).await
+    }
+
+    /// Stops a running TWAP on `coin`. Slices that already filled are kept.
+    pub async fn twap_cancel(&self, coin: &str, twap_id: u64) -> Result<ExchangeResponse> {
+        let action = Actions::TwapCancel(TwapCancel {
+            asset: self.assets.asset(coin)?,
+            twap_id,
+        });
+        self.post_l1_action(action).await
+    }
+
+    /// Schedules a cancel of all open orders at `time`, in milliseconds. The time has to be at
+    /// least 5 seconds in the future; `None` removes the scheduled cancel. See
+    /// [`DeadMansSwitch`](crate::exchange::dead_mans_switch::DeadMansSwitch) for keeping it
+    /// pushed forward automatically.
+    pub async fn schedule_cancel(&self, time: Option<u64>) -> Result<ExchangeResponse> {
+        self.post_l1_action(Actions::ScheduleCancel(ScheduleCancel { time }))
             .await
-            .map_err(|e| Error::JsonParse(e.to_string()))?;
-        debug!("Response: {output}");
-        serde_json::from_str(output).map_err(|e| Error::JsonParse(e.to_string()))
     }
 
     pub async fn perp_deploy_set_oracle(
@@ -113,10 +552,7 @@ impl ExchangeClient {
         dex: String,
         oracle_pxs: HashMap<String, String>,
         mark_pxs: Vec<HashMap<String, String>>,
-    ) -> Result<ExchangeResponseStatus> {
-        let wallet = &self.wallet;
-        let timestamp = next_nonce();
-
+    ) -> Result<ExchangeResponse> {
         // Convert HashMap to sorted Vec<(String, String)> as expected by the API
         let mut oracle_pxs_wire: Vec<(String, String)> = oracle_pxs.into_iter().collect();
         oracle_pxs_wire.sort_by(|a, b| a.0.cmp(&b.0));
@@ -136,14 +572,827 @@ impl ExchangeClient {
             mark_pxs: mark_pxs_wire,
         };
 
-        let perp_deploy = PerpDeploy { set_oracle };
+        let action = Actions::PerpDeploy(PerpDeploy::SetOracle(set_oracle));
+        self.post_l1_action(action).await
+    }
+
+    /// Registers a new asset on the builder-deployed `dex`. `schema` is required when the
+    /// asset is the first of the dex and creates it. `max_gas` caps what the deployer pays in
+    /// the deploy auction, `None` accepting the current price.
+    pub async fn perp_deploy_register_asset(
+        &self,
+        dex: &str,
+        max_gas: Option<u64>,
+        asset_request: RegisterAssetRequest,
+        schema: Option<PerpDexSchema>,
+    ) -> Result<ExchangeResponse> {
+        let register_asset = RegisterAsset {
+            max_gas,
+            asset_request,
+            dex: dex.to_string(),
+            schema,
+        };
+        let action = Actions::PerpDeploy(PerpDeploy::RegisterAsset(register_asset));
+        self.post_l1_action(action).await
+    }
+
+    /// Sets the funding multipliers of assets on a deployed dex, keyed by coin.
+    pub async fn perp_deploy_set_funding_multipliers(
+        &self,
+        multipliers: HashMap<String, String>,
+    ) -> Result<ExchangeResponse> {
+        let mut multipliers: Vec<(String, String)> = multipliers.into_iter().collect();
+        multipliers.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let action = Actions::PerpDeploy(PerpDeploy::SetFundingMultipliers(multipliers));
+        self.post_l1_action(action).await
+    }
+
+    /// Halts or resumes trading of `coin` on a deployed dex. Halting cancels its open orders
+    /// and settles positions at the mark price.
+    pub async fn perp_deploy_halt_trading(
+        &self,
+        coin: &str,
+        is_halted: bool,
+    ) -> Result<ExchangeResponse> {
+        let action = Actions::PerpDeploy(PerpDeploy::HaltTrading(HaltTrading {
+            coin: coin.to_string(),
+            is_halted,
+        }));
+        self.post_l1_action(action).await
+    }
+
+    /// Sets the open interest caps of assets on a deployed dex, keyed by coin, in USD.
+    pub async fn perp_deploy_set_open_interest_caps(
+        &self,
+        caps: HashMap<String, u64>,
+    ) -> Result<ExchangeResponse> {
+        let mut caps: Vec<(String, u64)> = caps.into_iter().collect();
+        caps.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let action = Actions::PerpDeploy(PerpDeploy::SetOpenInterestCaps(caps));
+        self.post_l1_action(action).await
+    }
+
+    /// Sets the address that receives the deployer's share of the dex's trading fees.
+    pub async fn perp_deploy_set_fee_recipient(
+        &self,
+        dex: &str,
+        fee_recipient: Address,
+    ) -> Result<ExchangeResponse> {
+        let action = Actions::PerpDeploy(PerpDeploy::SetFeeRecipient(SetFeeRecipient {
+            dex: dex.to_string(),
+            fee_recipient: format!("{fee_recipient:#x}"),
+        }));
+        self.post_l1_action(action).await
+    }
+
+    pub async fn usd_transfer(&self, amount: &str, destination: &str) -> Result<ExchangeResponse> {
+        let timestamp = self.next_nonce()?;
+        let usd_send = UsdSend {
+            signature_chain_id: self.chain.signature_chain_id(),
+            hyperliquid_chain: self.chain.hyperliquid_chain().to_string(),
+            destination: destination.to_string(),
+            amount: amount.to_string(),
+            time: timestamp,
+        };
+
+        self.post_user_action(&usd_send, Actions::UsdSend(usd_send.clone()), timestamp)
+            .await
+    }
+
+    pub async fn withdraw_from_bridge(
+        &self,
+        amount: &str,
+        destination: &str,
+    ) -> Result<ExchangeResponse> {
+        let timestamp = self.next_nonce()?;
+        let withdraw = Withdraw3 {
+            signature_chain_id: self.chain.signature_chain_id(),
+            hyperliquid_chain: self.chain.hyperliquid_chain().to_string(),
+            destination: destination.to_string(),
+            amount: amount.to_string(),
+            time: timestamp,
+        };
+
+        self.post_user_action(&withdraw, Actions::Withdraw3(withdraw.clone()), timestamp)
+            .await
+    }
+
+    /// `token` is in `NAME:tokenId` form, e.g. `"PURR:0xc4bf3f870c0e9465323c0b6ed28096c2"`.
+    pub async fn spot_transfer(
+        &self,
+        amount: &str,
+        destination: &str,
+        token: &str,
+    ) -> Result<ExchangeResponse> {
+        let timestamp = self.next_nonce()?;
+        let spot_send = SpotSend {
+            signature_chain_id: self.chain.signature_chain_id(),
+            hyperliquid_chain: self.chain.hyperliquid_chain().to_string(),
+            destination: destination.to_string(),
+            token: token.to_string(),
+            amount: amount.to_string(),
+            time: timestamp,
+        };
+
+        self.post_user_action(&spot_send, Actions::SpotSend(spot_send.clone()), timestamp)
+            .await
+    }
+
+    /// Moves `amount` of USDC between the spot and perp balances.
+    pub async fn class_transfer(&self, amount: &str, to_perp: bool) -> Result<ExchangeResponse> {
+        let timestamp = self.next_nonce()?;
+        let class_transfer = ClassTransfer {
+            signature_chain_id: self.chain.signature_chain_id(),
+            hyperliquid_chain: self.chain.hyperliquid_chain().to_string(),
+            amount: amount.to_string(),
+            to_perp,
+            nonce: timestamp,
+        };
+
+        self.post_user_action(
+            &class_transfer,
+            Actions::UsdClassTransfer(class_transfer.clone()),
+            timestamp,
+        )
+        .await
+    }
+
+    /// Sends a token between perp dexes, spot and other users. An empty dex name is the
+    /// default perp dex and `"spot"` is the spot balance.
+    pub async fn send_asset(
+        &self,
+        destination: &str,
+        source_dex: &str,
+        destination_dex: &str,
+        token: &str,
+        amount: &str,
+        from_sub_account: Option<&str>,
+    ) -> Result<ExchangeResponse> {
+        let timestamp = self.next_nonce()?;
+        let send_asset = SendAsset {
+            signature_chain_id: self.chain.signature_chain_id(),
+            hyperliquid_chain: self.chain.hyperliquid_chain().to_string(),
+            destination: destination.to_string(),
+            source_dex: source_dex.to_string(),
+            destination_dex: destination_dex.to_string(),
+            token: token.to_string(),
+            amount: amount.to_string(),
+            from_sub_account: from_sub_account.unwrap_or_default().to_string(),
+            nonce: timestamp,
+        };
+
+        self.post_user_action(
+            &send_asset,
+            Actions::SendAsset(send_asset.clone()),
+            timestamp,
+        )
+        .await
+    }
+
+    /// Creates a sub-account of this client's account. The response data is
+    /// [`ExchangeResponseData::CreateSubAccount`](crate::ExchangeResponseData::CreateSubAccount)
+    /// with the new sub-account's address.
+    pub async fn create_sub_account(&self, name: &str) -> Result<ExchangeResponse> {
+        let action = Actions::CreateSubAccount(CreateSubAccount {
+            name: name.to_string(),
+        });
+        self.post_l1_action(action).await
+    }
+
+    /// Moves USDC between this account's perp balance and a sub-account's, into the
+    /// sub-account if `is_deposit`.
+    pub async fn sub_account_transfer(
+        &self,
+        sub_account: Address,
+        is_deposit: bool,
+        usd: Decimal,
+    ) -> Result<ExchangeResponse> {
+        let usd = usd_to_micros(usd)?;
+        let action = Actions::SubAccountTransfer(SubAccountTransfer {
+            sub_account_user: format!("{sub_account:#x}"),
+            is_deposit,
+            usd,
+        });
+        self.post_l1_action(action).await
+    }
+
+    /// Moves a spot token between this account and a sub-account, into the sub-account if
+    /// `is_deposit`. `token` is in `NAME:tokenId` form, as for [`Self::spot_transfer`].
+    pub async fn sub_account_spot_transfer(
+        &self,
+        sub_account: Address,
+        is_deposit: bool,
+        token: &str,
+        amount: &str,
+    ) -> Result<ExchangeResponse> {
+        let action = Actions::SubAccountSpotTransfer(SubAccountSpotTransfer {
+            sub_account_user: format!("{sub_account:#x}"),
+            is_deposit,
+            token: token.to_string(),
+            amount: amount.to_string(),
+        });
+        self.post_l1_action(action).await
+    }
+
+    /// Moves `wei` of HYPE (8 decimals) from the spot balance into the staking balance.
+    pub async fn c_deposit(&self, wei: u64) -> Result<ExchangeResponse> {
+        let timestamp = self.next_nonce()?;
+        let c_deposit = CDeposit {
+            signature_chain_id: self.chain.signature_chain_id(),
+            hyperliquid_chain: self.chain.hyperliquid_chain().to_string(),
+            wei,
+            nonce: timestamp,
+        };
+
+        self.post_user_action(&c_deposit, Actions::CDeposit(c_deposit.clone()), timestamp)
+            .await
+    }
+
+    /// Moves `wei` of undelegated HYPE (8 decimals) from the staking balance back to spot.
+    /// The withdrawal is pending for 7 days before it arrives.
+    pub async fn c_withdraw(&self, wei: u64) -> Result<ExchangeResponse> {
+        let timestamp = self.next_nonce()?;
+        let c_withdraw = CWithdraw {
+            signature_chain_id: self.chain.signature_chain_id(),
+            hyperliquid_chain: self.chain.hyperliquid_chain().to_string(),
+            wei,
+            nonce: timestamp,
+        };
+
+        self.post_user_action(
+            &c_withdraw,
+            Actions::CWithdraw(c_withdraw.clone()),
+            timestamp,
+        )
+        .await
+    }
+
+    /// Delegates `wei` of staked HYPE (8 decimals) to `validator`, or undelegates it if
+    /// `is_undelegate`. Delegations are locked for a day after they are made.
+    pub async fn token_delegate(
+        &self,
+        validator: Address,
+        wei: u64,
+        is_undelegate: bool,
+    ) -> Result<ExchangeResponse> {
+        let timestamp = self.next_nonce()?;
+        let token_delegate = TokenDelegate {
+            signature_chain_id: self.chain.signature_chain_id(),
+            hyperliquid_chain: self.chain.hyperliquid_chain().to_string(),
+            validator,
+            wei,
+            is_undelegate,
+            nonce: timestamp,
+        };
+
+        self.post_user_action(
+            &token_delegate,
+            Actions::TokenDelegate(token_delegate.clone()),
+            timestamp,
+        )
+        .await
+    }
+
+    /// Deposits USDC into `vault`, or withdraws from it if not `is_deposit`.
+    pub async fn vault_transfer(
+        &self,
+        vault: Address,
+        is_deposit: bool,
+        usd: Decimal,
+    ) -> Result<ExchangeResponse> {
+        let usd = usd_to_micros(usd)?;
+        let action = Actions::VaultTransfer(VaultTransfer {
+            vault_address: format!("{vault:#x}"),
+            is_deposit,
+            usd,
+        });
+        self.post_l1_action(action).await
+    }
+
+    /// Creates a vault led by this client's account, seeded with `initial_usd` of its USDC.
+    /// The response data is
+    /// [`ExchangeResponseData::CreateVault`](crate::ExchangeResponseData::CreateVault) with the
+    /// new vault's address.
+    pub async fn create_vault(
+        &self,
+        name: &str,
+        description: &str,
+        initial_usd: Decimal,
+    ) -> Result<ExchangeResponse> {
+        let timestamp = self.next_nonce()?;
+        let action = Actions::CreateVault(CreateVault {
+            name: name.to_string(),
+            description: description.to_string(),
+            initial_usd: usd_to_micros(initial_usd)?,
+            nonce: timestamp,
+        });
+        self.post_l1_action_with_nonce(action, timestamp).await
+    }
+
+    /// Changes the settings of a vault this account leads. `None` leaves a setting as it is.
+    pub async fn vault_modify(
+        &self,
+        vault: Address,
+        allow_deposits: Option<bool>,
+        always_close_on_withdraw: Option<bool>,
+    ) -> Result<ExchangeResponse> {
+        let action = Actions::VaultModify(VaultModify {
+            vault_address: format!("{vault:#x}"),
+            allow_deposits,
+            always_close_on_withdraw,
+        });
+        self.post_l1_action(action).await
+    }
+
+    /// Distributes `usd` of a vault's balance to its followers, pro rata to their equity.
+    pub async fn vault_distribute(&self, vault: Address, usd: Decimal) -> Result<ExchangeResponse> {
+        let action = Actions::VaultDistribute(VaultDistribute {
+            vault_address: format!("{vault:#x}"),
+            usd: usd_to_micros(usd)?,
+        });
+        self.post_l1_action(action).await
+    }
+
+    /// Generates a fresh API wallet and approves it with this client's wallet. Returns the new
+    /// agent's key together with the exchange response.
+    pub async fn approve_agent(
+        &self,
+        name: Option<&str>,
+    ) -> Result<(PrivateKeySigner, ExchangeResponse)> {
+        let agent = PrivateKeySigner::random();
+        let response = self.approve_agent_address(agent.address(), name).await?;
+        Ok((agent, response))
+    }
+
+    /// Approves an agent whose key is held elsewhere, e.g. by a [`RemoteSigner`].
+    ///
+    /// [`RemoteSigner`]: crate::signature::signer::RemoteSigner
+    pub async fn approve_agent_address(
+        &self,
+        agent_address: Address,
+        name: Option<&str>,
+    ) -> Result<ExchangeResponse> {
+        self.ensure_master_signer()?;
+        let timestamp = self.next_nonce()?;
+        let approve_agent = ApproveAgent {
+            signature_chain_id: self.chain.signature_chain_id(),
+            hyperliquid_chain: self.chain.hyperliquid_chain().to_string(),
+            agent_address,
+            agent_name: name.map(str::to_string),
+            nonce: timestamp,
+        };
+
+        self.post_user_action(
+            &approve_agent,
+            Actions::ApproveAgent(approve_agent.clone()),
+            timestamp,
+        )
+        .await
+    }
+
+    /// Approves a replacement for the named agent when it is missing or expires within
+    /// `min_remaining`. Approving under an existing name replaces the old key.
+    pub async fn rotate_agent(
+        &self,
+        name: &str,
+        min_remaining: Duration,
+    ) -> Result<Option<(PrivateKeySigner, ExchangeResponse)>> {
+        self.ensure_master_signer()?;
+        let agents = self
+            .info_client()
+            .extra_agents(self.wallet.address())
+            .await?;
+        let deadline = now_timestamp_ms() + min_remaining.as_millis() as u64;
+        let expiring = agents
+            .iter()
+            .find(|agent| agent.name == name)
+            .is_none_or(|agent| agent.valid_until <= deadline);
+
+        if !expiring {
+            return Ok(None);
+        }
+        self.approve_agent(Some(name)).await.map(Some)
+    }
+
+    /// The exchange only accepts `ApproveAgent` signed by the master account, so a client
+    /// acting for `account_address` through an agent cannot approve others.
+    fn ensure_master_signer(&self) -> Result<()> {
+        match self.account_address {
+            Some(account_address) if account_address != self.wallet.address() => {
+                Err(Error::MasterSignerRequired)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::test_support::{client, info, posted, serve, wallet};
+
+    const MASTER: &str = "0x1719884eb866cb12b2287399b15f7db5e7d775ea";
+
+    #[tokio::test]
+    async fn test_user_signed_transfers() -> Result<()> {
+        let (url, requests) = serve(info).await;
+        let client = client(&url);
+        let destination = "0x0d1d9635d0640821d15e323ac8adadfa9c111414";
+        client.usd_transfer("1", destination).await?;
+        client.withdraw_from_bridge("2", destination).await?;
+        client
+            .spot_transfer("3", destination, "PURR:0xc1fb593aeffbeb02f85e0308e9956a90")
+            .await?;
+        client.class_transfer("4.5", true).await?;
+        client
+            .send_asset(destination, "", "spot", "USDC", "5", None)
+            .await?;
+
+        let expected = [
+            ("usdSend", "HyperliquidTransaction:UsdSend", "time"),
+            ("withdraw3", "HyperliquidTransaction:Withdraw", "time"),
+            ("spotSend", "HyperliquidTransaction:SpotSend", "time"),
+            (
+                "usdClassTransfer",
+                "HyperliquidTransaction:UsdClassTransfer",
+                "nonce",
+            ),
+            ("sendAsset", "HyperliquidTransaction:SendAsset", "nonce"),
+        ];
+        let posted = posted(&requests);
+        assert_eq!(posted.len(), expected.len());
+        for (body, (action_type, primary_type, nonce_field)) in posted.iter().zip(expected) {
+            assert_eq!(body["action"]["type"], action_type);
+            assert_eq!(body["action"][nonce_field], body["nonce"]);
+
+            let signed: SignedAction = serde_json::from_value(body.clone()).unwrap();
+            let typed_data = signed
+                .action
+                .typed_data(signed.nonce, None, None, Chain::Testnet)?;
+            let typed_data = serde_json::to_value(typed_data).unwrap();
+            assert_eq!(typed_data["primaryType"], primary_type);
+            assert_eq!(typed_data["message"][nonce_field], body["nonce"]);
+            assert_eq!(
+                signed.recover_signer(Chain::Testnet)?,
+                client.wallet.address()
+            );
+        }
+
+        let signed: SignedAction = serde_json::from_value(posted[3].clone()).unwrap();
+        let typed_data = signed
+            .action
+            .typed_data(signed.nonce, None, None, Chain::Testnet)?;
+        assert_eq!(
+            serde_json::to_value(typed_data).unwrap(),
+            json!({
+                "domain": {
+                    "name": "HyperliquidSignTransaction",
+                    "version": "1",
+                    "chainId": 998,
+                    "verifyingContract": "0x0000000000000000000000000000000000000000",
+                },
+                "types": {
+                    "EIP712Domain": [
+                        {"name": "name", "type": "string"},
+                        {"name": "version", "type": "string"},
+                        {"name": "chainId", "type": "uint256"},
+                        {"name": "verifyingContract", "type": "address"},
+                    ],
+                    "HyperliquidTransaction:UsdClassTransfer": [
+                        {"name": "hyperliquidChain", "type": "string"},
+                        {"name": "amount", "type": "string"},
+                        {"name": "toPerp", "type": "bool"},
+                        {"name": "nonce", "type": "uint64"},
+                    ],
+                },
+                "primaryType": "HyperliquidTransaction:UsdClassTransfer",
+                "message": {
+                    "hyperliquidChain": "Testnet",
+                    "amount": "4.5",
+                    "toPerp": true,
+                    "nonce": signed.nonce,
+                },
+            })
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_builder_dex_orders() -> Result<()> {
+        let (url, requests) = serve(info).await;
+        let info_client = InfoClient::from_http_client(HttpClient {
+            client: Client::new(),
+            base_url: url.clone(),
+        });
+        let mut client = ExchangeClient::new(
+            Some(info_client),
+            wallet(),
+            Some(BaseUrl::Testnet),
+            None,
+            None,
+        )
+        .await?;
+        client.http_client.base_url = url;
+
+        let asset = client.asset("xyz:XYZ100")?;
+        assert_eq!(asset, 110_000);
+        client
+            .order(ClientOrderRequest {
+                asset,
+                is_buy: true,
+                reduce_only: false,
+                limit_px: "25.5".parse()?,
+                sz: "0.1".parse()?,
+                cloid: None,
+                order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Gtc }),
+            })
+            .await?;
+
+        let posted = posted(&requests);
+        assert_eq!(posted.len(), 1);
+        assert_eq!(posted[0]["action"]["orders"][0]["a"], 110_000);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_vault_signs_its_nonce() -> Result<()> {
+        let (url, requests) = serve(info).await;
+        client(&url)
+            .create_vault("grid", "a grid vault", Decimal::ONE_HUNDRED)
+            .await?;
+
+        let posted = posted(&requests);
+        assert_eq!(posted[0]["action"]["nonce"], posted[0]["nonce"]);
+        assert_eq!(posted[0]["action"]["initialUsd"], 100_000_000);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_position_tpsl() -> Result<()> {
+        let (url, requests) = serve(info).await;
+        let client = client(&url);
+
+        assert!(matches!(
+            client.position_tpsl("ETH", None, None).await,
+            Err(Error::InvalidOrder(_))
+        ));
+        assert!(matches!(
+            client
+                .position_tpsl("BTC", Some("60000".parse()?), None)
+                .await,
+            Err(Error::NoPosition(_))
+        ));
+        assert!(posted(&requests).is_empty());
+
+        client
+            .position_tpsl("ETH", Some("2500".parse()?), Some("3500".parse()?))
+            .await?;
+        let posted = posted(&requests);
+        let action = &posted[0]["action"];
+        assert_eq!(action["grouping"], "positionTpsl");
+        let orders = action["orders"].as_array().unwrap();
+        assert_eq!(orders.len(), 2);
+        for order in orders {
+            // Closing the short buys back its whole size.
+            assert_eq!(order["b"], true);
+            assert_eq!(order["s"], "1.5");
+            assert_eq!(order["r"], true);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_builder_dex_position_tpsl() -> Result<()> {
+        let (url, requests) = serve(info).await;
+        let client = client(&url);
+
+        client
+            .position_tpsl("xyz:XYZ100", None, Some("20".parse()?))
+            .await?;
+        let posted = posted(&requests);
+        let orders = posted[0]["action"]["orders"].as_array().unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0]["a"], 110_000);
+        // Closing the long sells its whole size.
+        assert_eq!(orders[0]["b"], false);
+        assert_eq!(orders[0]["s"], "2");
+        assert_eq!(orders[0]["t"]["trigger"]["tpsl"], "sl");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_market_orders_round_the_slippage_price() -> Result<()> {
+        let (url, requests) = serve(info).await;
+        let client = client(&url);
+
+        client
+            .market_open("BTC", true, "0.01".parse()?, 0.01)
+            .await?;
+        client
+            .market_open("BTC", false, "0.01".parse()?, 0.01)
+            .await?;
+        client
+            .market_open("ETH", false, "0.5".parse()?, 0.05)
+            .await?;
+        client.market_close("ETH", None, 0.05).await?;
+        assert!(matches!(
+            client.market_close("BTC", None, 0.05).await,
+            Err(Error::NoPosition(_))
+        ));
+
+        let orders: Vec<Value> = posted(&requests)
+            .iter()
+            .map(|body| body["action"]["orders"][0].clone())
+            .collect();
+        assert_eq!(orders.len(), 4);
+        // 50500.505 and 49500.495 keep five significant figures.
+        assert_eq!(orders[0]["p"], "50501");
+        assert_eq!(orders[0]["b"], true);
+        assert_eq!(orders[1]["p"], "49500");
+        assert_eq!(orders[1]["b"], false);
+        // 2851.3015 and 3151.4385 keep five significant figures.
+        assert_eq!(orders[2]["p"], "2851.3");
+        assert_eq!(orders[2]["r"], false);
+        // Closing the short buys back its whole size.
+        assert_eq!(orders[3]["p"], "3151.4");
+        assert_eq!(orders[3]["b"], true);
+        assert_eq!(orders[3]["s"], "1.5");
+        assert_eq!(orders[3]["r"], true);
+        for order in &orders {
+            assert_eq!(order["t"]["limit"]["tif"], "Ioc");
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_builder_dex_market_orders() -> Result<()> {
+        let (url, requests) = serve(info).await;
+        let client = client(&url);
+
+        client
+            .market_open("xyz:XYZ100", true, "0.5".parse()?, 0.05)
+            .await?;
+        client.market_close("xyz:XYZ100", None, 0.01).await?;
+
+        let orders: Vec<Value> = posted(&requests)
+            .iter()
+            .map(|body| body["action"]["orders"][0].clone())
+            .collect();
+        assert_eq!(orders.len(), 2);
+        // 26.37915 and 24.87177 keep five significant figures.
+        assert_eq!(orders[0]["a"], 110_000);
+        assert_eq!(orders[0]["p"], "26.379");
+        assert_eq!(orders[0]["b"], true);
+        // Closing the long sells its whole size.
+        assert_eq!(orders[1]["p"], "24.872");
+        assert_eq!(orders[1]["b"], false);
+        assert_eq!(orders[1]["s"], "2");
+        assert_eq!(orders[1]["r"], true);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_twap_order_rejects_off_lot_sizes() -> Result<()> {
+        let (url, requests) = serve(info).await;
+        let client = client(&url);
+
+        for sz in ["0", "0.00001", "1.23456"] {
+            assert!(matches!(
+                client
+                    .twap_order("ETH", true, sz.parse()?, 30, false, false)
+                    .await,
+                Err(Error::InvalidOrder(_))
+            ));
+        }
+        assert!(posted(&requests).is_empty());
+
+        client
+            .twap_order("ETH", true, "1.2345".parse()?, 30, false, false)
+            .await?;
+        let posted = posted(&requests);
+        assert_eq!(posted[0]["action"]["twap"]["a"], 1);
+        assert_eq!(posted[0]["action"]["twap"]["s"], "1.2345");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_leverage_validation() -> Result<()> {
+        let (url, requests) = serve(info).await;
+        let client = client(&url);
+
+        for (coin, leverage, is_cross) in [("ETH", 0, true), ("ETH", 26, false), ("HYPE", 5, true)]
+        {
+            assert!(matches!(
+                client.update_leverage(coin, leverage, is_cross).await,
+                Err(Error::InvalidLeverage(_))
+            ));
+        }
+        assert!(matches!(
+            client.update_leverage("DOGE", 5, true).await,
+            Err(Error::AssetNotFound)
+        ));
+        assert!(posted(&requests).is_empty());
+
+        client.update_leverage("ETH", 25, true).await?;
+        client.update_leverage("HYPE", 10, false).await?;
+        client
+            .update_isolated_margin("HYPE", Decimal::new(-125, 1))
+            .await?;
+        let posted = posted(&requests);
+        assert_eq!(
+            posted[0]["action"],
+            json!({"type": "updateLeverage", "asset": 1, "isCross": true, "leverage": 25})
+        );
+        assert_eq!(
+            posted[1]["action"],
+            json!({"type": "updateLeverage", "asset": 2, "isCross": false, "leverage": 10})
+        );
+        assert_eq!(
+            posted[2]["action"],
+            json!({"type": "updateIsolatedMargin", "asset": 2, "isBuy": true, "ntli": -12_500_000})
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_usd_to_micros() {
+        assert_eq!(
+            usd_to_micros(Decimal::new(123_456_789, 7)).unwrap(),
+            12_345_679
+        );
+        assert_eq!(usd_to_micros(Decimal::ZERO).unwrap(), 0);
+        assert!(matches!(
+            usd_to_micros(Decimal::NEGATIVE_ONE),
+            Err(Error::InvalidAmount(_))
+        ));
+        assert!(matches!(
+            usd_to_micros(Decimal::MAX),
+            Err(Error::InvalidAmount(_))
+        ));
+        assert_eq!(
+            signed_usd_to_micros(Decimal::new(-125, 1)).unwrap(),
+            -12_500_000
+        );
+        assert!(matches!(
+            signed_usd_to_micros(Decimal::MIN),
+            Err(Error::InvalidAmount(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rotate_agent_requires_the_master_signer() -> Result<()> {
+        let (url, requests) = serve(info).await;
+        let client = ExchangeClient {
+            account_address: Some(MASTER.parse().unwrap()),
+            ..client(&url)
+        };
+
+        assert!(matches!(
+            client.rotate_agent("bot", Duration::from_secs(3600)).await,
+            Err(Error::MasterSignerRequired)
+        ));
+        assert!(matches!(
+            client.approve_agent(Some("bot")).await,
+            Err(Error::MasterSignerRequired)
+        ));
+        assert!(requests.lock().unwrap().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rotate_agent_renews_expiring_agents() -> Result<()> {
+        let (url, requests) = serve(info).await;
+        let client = client(&url);
 
-        let action = Actions::PerpDeploy(perp_deploy);
-        let connection_id = action.hash(timestamp, self.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
-        let is_mainnet = self.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
+        let rotated = client
+            .rotate_agent("bot", Duration::from_secs(3600))
+            .await?;
+        assert!(rotated.is_none());
+        assert!(posted(&requests).is_empty());
 
-        self.post(action, signature, timestamp).await
+        for name in ["old", "new"] {
+            let (agent, _) = client
+                .rotate_agent(name, Duration::from_secs(3600))
+                .await?
+                .expect("expiring and missing agents are renewed");
+            let posted = posted(&requests);
+            let action = &posted.last().unwrap()["action"];
+            assert_eq!(action["type"], "approveAgent");
+            assert_eq!(action["agentName"], name);
+            assert_eq!(
+                action["agentAddress"]
+                    .as_str()
+                    .unwrap()
+                    .parse::<Address>()
+                    .unwrap(),
+                agent.address()
+            );
+        }
+        assert_eq!(posted(&requests).len(), 2);
+        Ok(())
     }
 }
@@ -1,9 +1,14 @@
+use std::sync::Arc;
+
 use alloy::primitives::{keccak256, Address, B256};
+use rust_decimal::Decimal;
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    eip712::Eip712,
+    assets::AssetResolver,
+    eip712::{Eip712, TypedData},
+    enums::Tif,
     errors::Error,
     exchange::{
         actions::*,
@@ -11,29 +16,22 @@ use crate::{
         cancel::{
             CancelRequest, CancelRequestCloid, ClientCancelRequest, ClientCancelRequestCloid,
         },
+        exchange_client::signed_usd_to_micros,
         modify::{ClientModifyRequest, ModifyRequest},
-        order::{ClientLimit, ClientOrder, ClientOrderRequest, MarketOrderParams, SetTpSlParams},
+        order::{
+            BracketOrder, ClientLimit, ClientOrder, ClientOrderRequest, Grouping,
+            MarketOrderParams, SetTpSlParams,
+        },
     },
-    helpers::{next_nonce, uuid_to_hex_string},
+    helpers::{float_to_string_for_hashing, Chain},
+    nonce::NonceManager,
     prelude::*,
-    signature::create_signature::encode_l1_action,
+    signature::create_signature::{encode_l1_action, l1_typed_data},
 };
 use serde_json::Value;
 
 use super::{dtos::MessageResponse, dtos::SpotTransferRequest};
 
-#[cfg(not(feature = "testnet"))]
-const HYPERLIQUID_CHAIN: &str = "Mainnet";
-
-#[cfg(feature = "testnet")]
-const HYPERLIQUID_CHAIN: &str = "Testnet";
-
-#[cfg(not(feature = "testnet"))]
-const SIGNATURE_CHAIN_ID: u64 = 999;
-
-#[cfg(feature = "testnet")]
-const SIGNATURE_CHAIN_ID: u64 = 998;
-
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "type")]
 #[serde(rename_all = "camelCase")]
@@ -48,18 +46,36 @@ pub enum Actions {
     ApproveAgent(ApproveAgent),
     Withdraw3(Withdraw3),
     VaultTransfer(VaultTransfer),
+    CreateVault(CreateVault),
+    VaultModify(VaultModify),
+    VaultDistribute(VaultDistribute),
     SpotSend(SpotSend),
     SetReferrer(SetReferrer),
     ApproveBuilderFee(ApproveBuilderFee),
     SendAsset(SendAsset),
     UsdClassTransfer(ClassTransfer),
+    CDeposit(CDeposit),
+    CWithdraw(CWithdraw),
+    TokenDelegate(TokenDelegate),
     EvmUserModify(EvmUserModify),
     ScheduleCancel(ScheduleCancel),
+    TwapOrder(TwapOrder),
+    TwapCancel(TwapCancel),
     PerpDeploy(PerpDeploy),
+    CreateSubAccount(CreateSubAccount),
+    SubAccountTransfer(SubAccountTransfer),
+    SubAccountSpotTransfer(SubAccountSpotTransfer),
 }
 
 impl Actions {
-    pub fn hash(&self, timestamp: u64, vault_address: Option<Address>) -> Result<B256> {
+    /// Hash of the action as L1 actions sign it. With `expires_after` (a timestamp in
+    /// milliseconds) set, the exchange rejects the action once that time has passed.
+    pub fn hash(
+        &self,
+        timestamp: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<u64>,
+    ) -> Result<B256> {
         let mut bytes =
             rmp_serde::to_vec_named(self).map_err(|e| Error::RmpParse(e.to_string()))?;
         bytes.extend(timestamp.to_be_bytes());
@@ -69,75 +85,190 @@ impl Actions {
         } else {
             bytes.push(0);
         }
+        if let Some(expires_after) = expires_after {
+            bytes.push(0);
+            bytes.extend(expires_after.to_be_bytes());
+        }
         Ok(keccak256(bytes))
     }
+
+    /// The payload of actions the account signs as EIP-712 typed data itself, `None` for
+    /// actions signed through the L1 agent scheme.
+    pub(crate) fn user_signed(&self) -> Option<&dyn Eip712> {
+        match self {
+            Actions::UsdSend(action) => Some(action),
+            Actions::ApproveAgent(action) => Some(action),
+            Actions::Withdraw3(action) => Some(action),
+            Actions::SpotSend(action) => Some(action),
+            Actions::ApproveBuilderFee(action) => Some(action),
+            Actions::SendAsset(action) => Some(action),
+            Actions::UsdClassTransfer(action) => Some(action),
+            Actions::CDeposit(action) => Some(action),
+            Actions::CWithdraw(action) => Some(action),
+            Actions::TokenDelegate(action) => Some(action),
+            _ => None,
+        }
+    }
+
+    /// The digest the exchange expects to be signed for this action. User-signed actions are
+    /// EIP-712 hashes of their own payload, everything else goes through the L1 agent scheme.
+    pub fn signing_hash(
+        &self,
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<u64>,
+        chain: Chain,
+    ) -> Result<B256> {
+        match self.user_signed() {
+            Some(payload) => Ok(payload.eip712_signing_hash()),
+            None => encode_l1_action(self.hash(nonce, vault_address, expires_after)?, chain),
+        }
+    }
+
+    /// The EIP-712 document behind [`Actions::signing_hash`], for wallets that sign typed data.
+    pub fn typed_data(
+        &self,
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<u64>,
+        chain: Chain,
+    ) -> Result<TypedData> {
+        match self.user_signed() {
+            Some(payload) => Ok(payload.typed_data()),
+            None => Ok(l1_typed_data(
+                self.hash(nonce, vault_address, expires_after)?,
+                chain,
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HashGenerator {
+    pub chain: Chain,
+    /// When set, orders are validated against the asset's tick and lot sizes.
+    pub assets: Option<Arc<AssetResolver>>,
+    /// Vault the L1 actions are hashed for. The same address has to be sent with the signed
+    /// payload.
+    pub vault_address: Option<Address>,
+    /// Default expiry of generated L1 actions, in milliseconds since the epoch.
+    pub expires_after: Option<u64>,
+    pub nonces: Arc<NonceManager>,
+    /// Address that will sign the generated messages. Nonces are tracked per signer; without
+    /// one they are taken above every signer's, see [`NonceManager::next_shared`].
+    pub signer: Option<Address>,
+}
+
+impl Default for HashGenerator {
+    fn default() -> Self {
+        HashGenerator::new(Chain::default())
+    }
 }
 
-pub struct HashGenerator {}
 impl HashGenerator {
-    pub async fn usd_send(destination: String, amount: String) -> Result<MessageResponse> {
-        let timestamp = next_nonce();
+    pub fn new(chain: Chain) -> HashGenerator {
+        HashGenerator {
+            chain,
+            assets: None,
+            vault_address: None,
+            expires_after: None,
+            nonces: NonceManager::global(),
+            signer: None,
+        }
+    }
+
+    pub fn with_expires_after(mut self, expires_after: u64) -> HashGenerator {
+        self.expires_after = Some(expires_after);
+        self
+    }
+
+    pub fn with_signer(mut self, signer: Address) -> HashGenerator {
+        self.signer = Some(signer);
+        self
+    }
+
+    pub fn with_nonce_manager(mut self, nonces: Arc<NonceManager>) -> HashGenerator {
+        self.nonces = nonces;
+        self
+    }
+
+    fn next_nonce(&self) -> Result<u64> {
+        match self.signer {
+            Some(signer) => self.nonces.next(signer),
+            None => self.nonces.next_shared(),
+        }
+    }
+
+    pub fn with_vault_address(mut self, vault_address: Address) -> HashGenerator {
+        self.vault_address = Some(vault_address);
+        self
+    }
+
+    pub fn with_assets(mut self, assets: Arc<AssetResolver>) -> HashGenerator {
+        self.assets = Some(assets);
+        self
+    }
+
+    pub fn asset(&self, coin: &str) -> Result<u32> {
+        self.assets
+            .as_ref()
+            .ok_or(Error::AssetNotFound)?
+            .asset(coin)
+    }
+
+    fn validate(&self, order: &ClientOrderRequest) -> Result<()> {
+        match &self.assets {
+            Some(assets) => assets.validate(order),
+            None => Ok(()),
+        }
+    }
+
+    pub async fn usd_send(&self, destination: String, amount: String) -> Result<MessageResponse> {
+        let timestamp = self.next_nonce()?;
         let usd_send = UsdSend {
-            signature_chain_id: 421614,
-            hyperliquid_chain: HYPERLIQUID_CHAIN.to_string(),
+            signature_chain_id: self.chain.signature_chain_id(),
+            hyperliquid_chain: self.chain.hyperliquid_chain().to_string(),
             destination: destination.to_string(),
             amount: amount.to_string(),
             time: timestamp,
         };
 
-        let message = usd_send.eip712_signing_hash();
-
-        Ok(MessageResponse {
-            action: Actions::UsdSend(usd_send),
-            message,
-            nonce: timestamp,
-        })
+        self.get_message_for_action(Actions::UsdSend(usd_send), Some(timestamp), None)
     }
 
     pub async fn approve_builder_fee(
+        &self,
         builder: String,
         max_fee_rate: String,
     ) -> Result<MessageResponse> {
         let builder = builder.to_lowercase().parse::<Address>().unwrap();
-        let timestamp = next_nonce();
+        let timestamp = self.next_nonce()?;
         let action = ApproveBuilderFee {
             builder,
             max_fee_rate,
             nonce: timestamp,
-            signature_chain_id: SIGNATURE_CHAIN_ID,
-            hyperliquid_chain: HYPERLIQUID_CHAIN.to_string(),
+            signature_chain_id: self.chain.signature_chain_id(),
+            hyperliquid_chain: self.chain.hyperliquid_chain().to_string(),
         };
 
-        let message = action.eip712_signing_hash();
-
-        Ok(MessageResponse {
-            action: Actions::ApproveBuilderFee(action),
-            message,
-            nonce: timestamp,
-        })
+        self.get_message_for_action(Actions::ApproveBuilderFee(action), Some(timestamp), None)
     }
 
-    pub async fn class_transfer(usdc: f64, to_perp: bool) -> Result<MessageResponse> {
-        // payload expects usdc without decimals
-        let usdc = (usdc * 1e6).round() as u64;
-        let timestamp = next_nonce();
+    pub async fn class_transfer(&self, usdc: f64, to_perp: bool) -> Result<MessageResponse> {
+        let timestamp = self.next_nonce()?;
         let action = ClassTransfer {
-            usdc,
+            signature_chain_id: self.chain.signature_chain_id(),
+            hyperliquid_chain: self.chain.hyperliquid_chain().to_string(),
+            amount: float_to_string_for_hashing(usdc),
             to_perp,
-            signature_chain_id: SIGNATURE_CHAIN_ID,
-            hyperliquid_chain: HYPERLIQUID_CHAIN.to_string(),
+            nonce: timestamp,
         };
 
-        let message = action.eip712_signing_hash();
-
-        Ok(MessageResponse {
-            action: Actions::UsdClassTransfer(action),
-            message,
-            nonce: timestamp,
-        })
+        self.get_message_for_action(Actions::UsdClassTransfer(action), Some(timestamp), None)
     }
 
     pub async fn send_asset(
+        &self,
         token: String,
         source_dex: String,
         destination_dex: String,
@@ -145,7 +276,7 @@ impl HashGenerator {
         amount: String,
         from_sub_account: String,
     ) -> Result<MessageResponse> {
-        let timestamp = next_nonce();
+        let timestamp = self.next_nonce()?;
         let perp_dex_class_transfer = SendAsset {
             token,
             source_dex,
@@ -154,63 +285,58 @@ impl HashGenerator {
             amount: amount.to_string(),
             from_sub_account,
             nonce: timestamp,
-            hyperliquid_chain: HYPERLIQUID_CHAIN.to_string(),
-            signature_chain_id: SIGNATURE_CHAIN_ID.into(),
+            hyperliquid_chain: self.chain.hyperliquid_chain().to_string(),
+            signature_chain_id: self.chain.signature_chain_id(),
         };
-        let message = perp_dex_class_transfer.eip712_signing_hash();
-
-        Ok(MessageResponse {
-            action: Actions::SendAsset(perp_dex_class_transfer),
-            message,
-            nonce: timestamp,
-        })
+        self.get_message_for_action(
+            Actions::SendAsset(perp_dex_class_transfer),
+            Some(timestamp),
+            None,
+        )
     }
 
-    pub async fn set_tp_sl(params: SetTpSlParams) -> Result<MessageResponse> {
+    pub async fn set_tp_sl(&self, params: SetTpSlParams) -> Result<MessageResponse> {
         let order = ClientOrderRequest {
             asset: params.asset,
             is_buy: params.is_buy,
             reduce_only: params.reduce_only,
-            limit_px: params.px.parse::<f64>().unwrap(),
-            sz: params.sz.parse::<f64>().unwrap(),
+            limit_px: params.px,
+            sz: params.sz,
             cloid: params.cloid,
             order_type: params.order_type,
         };
 
-        Self::get_message_for_order(vec![order], None)
+        self.get_message_for_order(vec![order], params.grouping, None)
     }
 
-    pub async fn market_open(params: MarketOrderParams) -> Result<MessageResponse> {
+    pub async fn market_open(&self, params: MarketOrderParams) -> Result<MessageResponse> {
         let order = ClientOrderRequest {
             asset: params.asset,
             is_buy: params.is_buy,
             reduce_only: params.reduce_only,
-            limit_px: params.px.parse::<f64>().unwrap(),
-            sz: params.sz.parse::<f64>().unwrap(),
+            limit_px: params.px,
+            sz: params.sz,
             cloid: params.cloid,
-            order_type: ClientOrder::Limit(ClientLimit {
-                tif: "Ioc".to_string(),
-            }),
+            order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Ioc }),
         };
 
-        Self::get_message_for_order(vec![order], None)
+        self.get_message_for_order(vec![order], Grouping::Na, None)
     }
-    pub async fn limit_open(params: MarketOrderParams) -> Result<MessageResponse> {
+    pub async fn limit_open(&self, params: MarketOrderParams) -> Result<MessageResponse> {
         let order = ClientOrderRequest {
             asset: params.asset,
             is_buy: params.is_buy,
             reduce_only: params.reduce_only,
-            limit_px: params.px.parse::<f64>().unwrap(),
-            sz: params.sz.parse::<f64>().unwrap(),
+            limit_px: params.px,
+            sz: params.sz,
             cloid: params.cloid,
-            order_type: ClientOrder::Limit(ClientLimit {
-                tif: "Gtc".to_string(),
-            }),
+            order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Gtc }),
         };
 
-        Self::get_message_for_order(vec![order], None)
+        self.get_message_for_order(vec![order], Grouping::Na, None)
     }
     pub async fn market_open_with_builder(
+        &self,
         params: MarketOrderParams,
         builder: BuilderInfo,
     ) -> Result<MessageResponse> {
@@ -218,62 +344,62 @@ impl HashGenerator {
             asset: params.asset,
             is_buy: params.is_buy,
             reduce_only: params.reduce_only,
-            limit_px: params.px.parse::<f64>().unwrap(),
-            sz: params.sz.parse::<f64>().unwrap(),
+            limit_px: params.px,
+            sz: params.sz,
             cloid: params.cloid,
-            order_type: ClientOrder::Limit(ClientLimit {
-                tif: "Ioc".to_string(),
-            }),
+            order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Ioc }),
         };
 
-        Self::order_with_builder(order, builder).await
+        self.order_with_builder(order, builder).await
     }
     pub async fn order_with_builder(
+        &self,
         order: ClientOrderRequest,
         builder: BuilderInfo,
     ) -> Result<MessageResponse> {
-        Self::bulk_order_with_builder(vec![order], builder).await
+        self.bulk_order_with_builder(vec![order], builder).await
     }
     pub async fn bulk_order_with_builder(
+        &self,
         orders: Vec<ClientOrderRequest>,
         mut builder: BuilderInfo,
     ) -> Result<MessageResponse> {
-        let timestamp = next_nonce();
+        let timestamp = self.next_nonce()?;
 
         builder.builder = builder.builder.to_lowercase();
 
         let mut transformed_orders = Vec::new();
 
         for order in orders {
+            self.validate(&order)?;
             transformed_orders.push(order.convert()?);
         }
 
         let action = Actions::Order(BulkOrder {
             orders: transformed_orders,
-            grouping: "na".to_string(),
+            grouping: Grouping::Na,
             builder: Some(builder),
         });
-        Self::get_message_for_action(action, Some(timestamp))
+        self.get_message_for_action(action, Some(timestamp), None)
     }
 
-    pub async fn cancel_order(cancel: ClientCancelRequest) -> Result<MessageResponse> {
-        let mut transformed_cancels = Vec::new();
-
-        transformed_cancels.push(CancelRequest {
+    pub async fn cancel_order(&self, cancel: ClientCancelRequest) -> Result<MessageResponse> {
+        let transformed_cancels = vec![CancelRequest {
             asset: cancel.asset,
             oid: cancel.oid,
-        });
+        }];
 
         let action = Actions::Cancel(BulkCancel {
             cancels: transformed_cancels,
         });
 
-        Self::get_message_for_action(action, None)
+        self.get_message_for_action(action, None, None)
     }
 
-    pub async fn bulk_modify(modifies: Vec<ClientModifyRequest>) -> Result<Value> {
+    pub async fn bulk_modify(&self, modifies: Vec<ClientModifyRequest>) -> Result<Value> {
         let mut transformed_modifies = Vec::new();
         for modify in modifies.into_iter() {
+            self.validate(&modify.order)?;
             transformed_modifies.push(ModifyRequest {
                 oid: modify.oid,
                 order: modify.order.convert()?,
@@ -290,13 +416,14 @@ impl HashGenerator {
     }
 
     pub async fn bulk_cancel_by_cloid(
+        &self,
         cancels: Vec<ClientCancelRequestCloid>,
     ) -> Result<MessageResponse> {
         let mut transformed_cancels: Vec<CancelRequestCloid> = Vec::new();
         for cancel in cancels.into_iter() {
             transformed_cancels.push(CancelRequestCloid {
                 asset: cancel.asset,
-                cloid: uuid_to_hex_string(cancel.cloid),
+                cloid: cancel.cloid,
             });
         }
 
@@ -304,114 +431,220 @@ impl HashGenerator {
             cancels: transformed_cancels,
         });
 
-        Self::get_message_for_action(action, None)
+        self.get_message_for_action(action, None, None)
     }
 
-    pub async fn update_leverage(request: UpdateLeverage) -> Result<MessageResponse> {
+    pub async fn update_leverage(&self, request: UpdateLeverage) -> Result<MessageResponse> {
         let action = Actions::UpdateLeverage(request);
-        Self::get_message_for_action(action, None)
+        self.get_message_for_action(action, None, None)
     }
 
-    pub async fn spot_transfer(request: SpotTransferRequest) -> Result<MessageResponse> {
+    pub async fn spot_transfer(&self, request: SpotTransferRequest) -> Result<MessageResponse> {
         let SpotTransferRequest {
             amount,
             destination,
             token,
         } = request;
 
-        let timestamp = next_nonce();
+        let timestamp = self.next_nonce()?;
 
         let spot_send = SpotSend {
-            signature_chain_id: SIGNATURE_CHAIN_ID.into(),
-            hyperliquid_chain: HYPERLIQUID_CHAIN.to_string(),
+            signature_chain_id: self.chain.signature_chain_id(),
+            hyperliquid_chain: self.chain.hyperliquid_chain().to_string(),
             destination: destination.to_string(),
             amount: amount.to_string(),
             time: timestamp,
             token: token.to_string(),
         };
         let action = Actions::SpotSend(spot_send);
-        Self::get_message_for_action(action, Some(timestamp))
+        self.get_message_for_action(action, Some(timestamp), None)
     }
 
     pub async fn update_isolated_margin(
-        amount: f64,
+        &self,
+        amount: Decimal,
         asset: u32,
         is_buy: bool,
         nonce: u64,
-    ) -> Result<Value> {
-        // let amount = (amount * 1_000_000.0).round() as i64;
+    ) -> Result<MessageResponse> {
+        // payload expects usd without decimals
+        let ntli = signed_usd_to_micros(amount)?;
 
         let action = Actions::UpdateIsolatedMargin(UpdateIsolatedMargin {
             asset,
             is_buy,
-            ntli: amount as i64,
+            ntli,
         });
-        let message = action.hash(nonce, None)?;
-        let action = serde_json::to_value(&message).map_err(|e| Error::JsonParse(e.to_string()))?;
+        self.get_message_for_action(action, Some(nonce), None)
+    }
 
-        Ok(action)
+    /// An entry order with its take-profit and stop-loss triggers, see [`BracketOrder`].
+    pub async fn bracket_order(&self, bracket: BracketOrder) -> Result<MessageResponse> {
+        self.get_message_for_order(bracket.into_orders(), Grouping::NormalTpsl, None)
     }
 
     pub fn get_message_for_order(
+        &self,
         orders: Vec<ClientOrderRequest>,
+        grouping: Grouping,
         builder: Option<BuilderInfo>,
     ) -> Result<MessageResponse> {
         let mut transformed_orders = Vec::new();
 
         for order in orders {
+            self.validate(&order)?;
             transformed_orders.push(order.convert()?);
         }
 
         let bulk_order = BulkOrder {
             orders: transformed_orders,
-            grouping: "na".to_string(),
+            grouping,
             builder,
         };
         let action = Actions::Order(bulk_order.clone());
 
-        Self::get_message_for_action(action, None)
+        self.get_message_for_action(action, None, None)
     }
 
-    pub fn get_message_for_action(action: Actions, nonce: Option<u64>) -> Result<MessageResponse> {
-        let nonce = nonce.unwrap_or(next_nonce());
-        let connection_id = action.hash(nonce, None)?;
-        let message: B256 = encode_l1_action(connection_id)?;
+    /// Builds the message to sign for `action`. `expires_after` overrides the generator's
+    /// default expiry; user-signed actions never expire and are not hashed for a vault.
+    pub fn get_message_for_action(
+        &self,
+        action: Actions,
+        nonce: Option<u64>,
+        expires_after: Option<u64>,
+    ) -> Result<MessageResponse> {
+        let nonce = match nonce {
+            Some(nonce) => nonce,
+            None => self.next_nonce()?,
+        };
+        let (vault_address, expires_after) = match action.user_signed() {
+            Some(_) => (None, None),
+            None => (self.vault_address, expires_after.or(self.expires_after)),
+        };
+        let message = action.signing_hash(nonce, vault_address, expires_after, self.chain)?;
+        let typed_data = action.typed_data(nonce, vault_address, expires_after, self.chain)?;
 
         Ok(MessageResponse {
             action,
             message,
             nonce,
+            vault_address,
+            expires_after,
+            typed_data,
         })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use alloy::primitives::b256;
 
     use super::*;
-    use crate::exchange::order::{Limit, Order, OrderRequest};
+    use crate::{enums::TpSl, exchange::order::ClientTrigger, Cloid};
 
     #[test]
-    fn test_limit_order_action_hashing() -> Result<()> {
-        let action = Actions::Order(BulkOrder {
-            orders: vec![OrderRequest {
+    fn test_order_action_hashing() -> Result<()> {
+        let orders = vec![
+            ClientOrderRequest {
                 asset: 1,
                 is_buy: true,
-                limit_px: "2000.0".to_string(),
-                sz: "3.5".to_string(),
                 reduce_only: false,
-                order_type: Order::Limit(Limit {
-                    tif: "Ioc".to_string(),
-                }),
+                limit_px: "2000.0".parse()?,
+                sz: "3.5".parse()?,
                 cloid: None,
-            }],
-            grouping: "na".to_string(),
+                order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Ioc }),
+            },
+            ClientOrderRequest {
+                asset: 4,
+                is_buy: false,
+                reduce_only: true,
+                limit_px: "1950.5".parse()?,
+                sz: "0.0123".parse()?,
+                cloid: Some(Cloid::from_bytes([
+                    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2,
+                ])),
+                order_type: ClientOrder::Trigger(ClientTrigger {
+                    is_market: true,
+                    trigger_px: "1950.5".parse()?,
+                    tpsl: TpSl::Sl,
+                }),
+            },
+        ];
+        let action = Actions::Order(BulkOrder {
+            orders: orders
+                .into_iter()
+                .map(ClientOrderRequest::convert)
+                .collect::<Result<_>>()?,
+            grouping: Grouping::Na,
             builder: None,
         });
-        let connection_id = action.hash(1583838, None)?;
 
-        println!("connection_id: {:?}", connection_id);
+        // Wire format and hash produced by the f64-based orders this replaced.
+        assert_eq!(
+            serde_json::to_string(&action).map_err(|e| Error::JsonParse(e.to_string()))?,
+            r#"{"type":"order","orders":[{"a":1,"b":true,"p":"2000","s":"3.5","r":false,"t":{"limit":{"tif":"Ioc"}}},{"a":4,"b":false,"p":"1950.5","s":"0.0123","r":true,"t":{"trigger":{"isMarket":true,"triggerPx":"1950.5","tpsl":"sl"}},"c":"0x00000000000000000000000000000002"}],"grouping":"na"}"#
+        );
+        assert_eq!(
+            action.hash(1583838, None, None)?,
+            b256!("5e24d5043ed26842226ea395ab3f166d25180b2a1bbb422d8ad43b306a7d4cd3")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_perp_deploy_serialization() -> Result<()> {
+        let action = Actions::PerpDeploy(PerpDeploy::HaltTrading(HaltTrading {
+            coin: "xyz:ABC".to_string(),
+            is_halted: true,
+        }));
+        assert_eq!(
+            serde_json::to_string(&action).map_err(|e| Error::JsonParse(e.to_string()))?,
+            r#"{"type":"perpDeploy","haltTrading":{"coin":"xyz:ABC","isHalted":true}}"#
+        );
+
+        let action = Actions::PerpDeploy(PerpDeploy::SetOpenInterestCaps(vec![(
+            "xyz:ABC".to_string(),
+            1_000_000,
+        )]));
+        assert_eq!(
+            serde_json::to_string(&action).map_err(|e| Error::JsonParse(e.to_string()))?,
+            r#"{"type":"perpDeploy","setOpenInterestCaps":[["xyz:ABC",1000000]]}"#
+        );
+        let bytes = rmp_serde::to_vec_named(&action).map_err(|e| Error::RmpParse(e.to_string()))?;
+        assert!(matches!(
+            rmp_serde::from_slice(&bytes).map_err(|e| Error::RmpParse(e.to_string()))?,
+            Actions::PerpDeploy(PerpDeploy::SetOpenInterestCaps(_))
+        ));
+        Ok(())
+    }
 
+    #[tokio::test]
+    async fn test_bracket_order_grouping() -> Result<()> {
+        let entry = ClientOrderRequest {
+            asset: 0,
+            is_buy: true,
+            reduce_only: false,
+            limit_px: "60000".parse()?,
+            sz: "0.1".parse()?,
+            cloid: None,
+            order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Gtc }),
+        };
+        let bracket = BracketOrder::new(entry)
+            .take_profit("66000".parse()?)
+            .stop_loss("57000".parse()?);
+        let message = HashGenerator::default().bracket_order(bracket).await?;
+
+        let action = serde_json::to_value(&message.action).unwrap();
+        assert_eq!(action["grouping"], "normalTpsl");
+        let orders = action["orders"].as_array().unwrap();
+        assert_eq!(orders.len(), 3);
+        for (order, tpsl) in orders[1..].iter().zip(["tp", "sl"]) {
+            assert_eq!(order["b"], false);
+            assert_eq!(order["r"], true);
+            assert_eq!(order["s"], "0.1");
+            assert_eq!(order["t"]["trigger"]["tpsl"], tpsl);
+        }
         Ok(())
     }
 }
@@ -1,9 +1,12 @@
 pub mod actions;
 pub mod builder;
 pub mod cancel;
+pub mod dead_mans_switch;
 pub mod exchange_client;
 pub mod hash_generator;
 
 pub mod dtos;
 pub mod modify;
 pub mod order;
+pub mod response;
+pub mod signed_action;
@@ -1,14 +1,15 @@
 use crate::{
-    helpers::{float_to_string_for_hashing, uuid_to_hex_string},
+    cloid::Cloid,
+    decimal::{Px, Sz},
+    enums::{Tif, TpSl},
     prelude::*,
 };
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
-use uuid::Uuid;
 
 #[derive(Deserialize, Serialize, Clone, Debug, ToSchema)]
 pub struct Limit {
-    pub tif: String,
+    pub tif: Tif,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
@@ -16,7 +17,7 @@ pub struct Limit {
 pub struct Trigger {
     pub is_market: bool,
     pub trigger_px: String,
-    pub tpsl: String,
+    pub tpsl: TpSl,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
@@ -26,6 +27,21 @@ pub enum Order {
     Trigger(Trigger),
 }
 
+/// How the orders of one bulk order relate to each other.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum Grouping {
+    /// Independent orders.
+    #[default]
+    Na,
+    /// An entry order followed by take-profit and/or stop-loss triggers for its size. The
+    /// triggers only become active once the entry fills.
+    NormalTpsl,
+    /// Take-profit and/or stop-loss triggers attached to the open position, resized as the
+    /// position changes.
+    PositionTpsl,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderRequest {
@@ -42,19 +58,19 @@ pub struct OrderRequest {
     #[serde(rename = "t", alias = "orderType")]
     pub order_type: Order,
     #[serde(rename = "c", alias = "cloid", skip_serializing_if = "Option::is_none")]
-    pub cloid: Option<String>,
+    pub cloid: Option<Cloid>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct ClientLimit {
-    pub tif: String,
+    pub tif: Tif,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct ClientTrigger {
     pub is_market: bool,
-    pub trigger_px: f64,
-    pub tpsl: String,
+    pub trigger_px: Px,
+    pub tpsl: TpSl,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -62,9 +78,9 @@ pub struct MarketOrderParams {
     pub asset: u32,
     pub reduce_only: bool,
     pub is_buy: bool,
-    pub sz: String,
-    pub px: String,
-    pub cloid: Option<Uuid>,
+    pub sz: Sz,
+    pub px: Px,
+    pub cloid: Option<Cloid>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -73,10 +89,12 @@ pub struct SetTpSlParams {
     pub asset: u32,
     pub reduce_only: bool,
     pub is_buy: bool,
-    pub sz: String,
-    pub px: String,
-    pub cloid: Option<Uuid>,
+    pub sz: Sz,
+    pub px: Px,
+    pub cloid: Option<Cloid>,
     pub order_type: ClientOrder,
+    #[serde(default)]
+    pub grouping: Grouping,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -91,33 +109,109 @@ pub struct ClientOrderRequest {
     pub asset: u32,
     pub is_buy: bool,
     pub reduce_only: bool,
-    pub limit_px: f64,
-    pub sz: f64,
-    pub cloid: Option<Uuid>,
+    pub limit_px: Px,
+    pub sz: Sz,
+    pub cloid: Option<Cloid>,
     pub order_type: ClientOrder,
 }
 
 impl ClientOrderRequest {
+    /// A reduce-only trigger order closing `sz` of a position, filled at market (bounded by
+    /// the trigger price) once `trigger_px` is reached.
+    pub fn tpsl(asset: u32, is_buy: bool, sz: Sz, trigger_px: Px, tpsl: TpSl) -> Self {
+        ClientOrderRequest {
+            asset,
+            is_buy,
+            reduce_only: true,
+            limit_px: trigger_px,
+            sz,
+            cloid: None,
+            order_type: ClientOrder::Trigger(ClientTrigger {
+                is_market: true,
+                trigger_px,
+                tpsl,
+            }),
+        }
+    }
+
     pub(crate) fn convert(self) -> Result<OrderRequest> {
         let order_type = match self.order_type {
             ClientOrder::Limit(limit) => Order::Limit(Limit { tif: limit.tif }),
             ClientOrder::Trigger(trigger) => Order::Trigger(Trigger {
-                trigger_px: float_to_string_for_hashing(trigger.trigger_px),
+                trigger_px: trigger.trigger_px.to_wire(),
                 is_market: trigger.is_market,
                 tpsl: trigger.tpsl,
             }),
         };
 
-        let cloid = self.cloid.map(uuid_to_hex_string);
-
         Ok(OrderRequest {
             asset: self.asset,
             is_buy: self.is_buy,
             reduce_only: self.reduce_only,
-            limit_px: float_to_string_for_hashing(self.limit_px),
-            sz: float_to_string_for_hashing(self.sz),
+            limit_px: self.limit_px.to_wire(),
+            sz: self.sz.to_wire(),
             order_type,
-            cloid,
+            cloid: self.cloid,
         })
     }
 }
+
+/// An entry order with linked take-profit and stop-loss triggers, sent as one
+/// [`Grouping::NormalTpsl`] bulk order.
+#[derive(Debug)]
+pub struct BracketOrder {
+    pub entry: ClientOrderRequest,
+    pub take_profit: Option<Px>,
+    pub stop_loss: Option<Px>,
+}
+
+impl BracketOrder {
+    pub fn new(entry: ClientOrderRequest) -> Self {
+        BracketOrder {
+            entry,
+            take_profit: None,
+            stop_loss: None,
+        }
+    }
+
+    pub fn take_profit(mut self, trigger_px: Px) -> Self {
+        self.take_profit = Some(trigger_px);
+        self
+    }
+
+    pub fn stop_loss(mut self, trigger_px: Px) -> Self {
+        self.stop_loss = Some(trigger_px);
+        self
+    }
+
+    /// The entry followed by its triggers, which close the entry's size on the other side.
+    pub(crate) fn into_orders(self) -> Vec<ClientOrderRequest> {
+        let BracketOrder {
+            entry,
+            take_profit,
+            stop_loss,
+        } = self;
+        let (asset, is_buy, sz) = (entry.asset, !entry.is_buy, entry.sz);
+
+        let mut orders = vec![entry];
+        if let Some(trigger_px) = take_profit {
+            orders.push(ClientOrderRequest::tpsl(
+                asset,
+                is_buy,
+                sz,
+                trigger_px,
+                TpSl::Tp,
+            ));
+        }
+        if let Some(trigger_px) = stop_loss {
+            orders.push(ClientOrderRequest::tpsl(
+                asset,
+                is_buy,
+                sz,
+                trigger_px,
+                TpSl::Sl,
+            ));
+        }
+        orders
+    }
+}
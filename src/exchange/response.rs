@@ -0,0 +1,320 @@
+use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cloid::Cloid,
+    decimal::{Px, Sz},
+    errors::Error,
+    prelude::*,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "status", content = "response", rename_all = "camelCase")]
+pub enum ExchangeResponse {
+    Ok(ExchangeResponseData),
+    Err(String),
+}
+
+impl ExchangeResponse {
+    /// Turns an `"err"` response into [`Error::Exchange`].
+    pub fn into_result(self) -> Result<ExchangeResponseData> {
+        match self {
+            ExchangeResponse::Ok(data) => Ok(data),
+            ExchangeResponse::Err(err) => Err(Error::Exchange(err)),
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        matches!(self, ExchangeResponse::Ok(_))
+    }
+
+    pub(crate) fn link_cloids(&mut self, cloids: Vec<Option<Cloid>>) {
+        if let ExchangeResponse::Ok(ExchangeResponseData::Order(statuses)) = self {
+            statuses.cloids = cloids;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+pub enum ExchangeResponseData {
+    #[serde(alias = "batchModify")]
+    Order(OrderStatuses),
+    Cancel(CancelStatuses),
+    TwapOrder(TwapOrderStatus),
+    TwapCancel(TwapCancelStatus),
+    /// Address of the created sub-account.
+    CreateSubAccount(Address),
+    /// Address of the created vault.
+    CreateVault(Address),
+    Default,
+    /// A response type this version does not know, as the raw `{"type", "data"}` object.
+    #[serde(untagged)]
+    Unknown(serde_json::Value),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OrderStatuses {
+    pub statuses: Vec<OrderResponseStatus>,
+    /// Cloids of the submitted orders, in request order.
+    #[serde(skip)]
+    cloids: Vec<Option<Cloid>>,
+}
+
+impl OrderStatuses {
+    /// Pairs every status with the index of the order it answers and that order's cloid.
+    pub fn outcomes(&self) -> impl Iterator<Item = OrderOutcome<'_>> {
+        self.statuses
+            .iter()
+            .enumerate()
+            .map(|(index, status)| OrderOutcome {
+                index,
+                cloid: self
+                    .cloids
+                    .get(index)
+                    .copied()
+                    .flatten()
+                    .or_else(|| status.cloid()),
+                status,
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OrderOutcome<'a> {
+    pub index: usize,
+    pub cloid: Option<Cloid>,
+    pub status: &'a OrderResponseStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum OrderResponseStatus {
+    Success,
+    WaitingForFill,
+    WaitingForTrigger,
+    Resting(RestingOrder),
+    Filled(FilledOrder),
+    Error(String),
+}
+
+impl OrderResponseStatus {
+    pub fn oid(&self) -> Option<u64> {
+        match self {
+            OrderResponseStatus::Resting(resting) => Some(resting.oid),
+            OrderResponseStatus::Filled(filled) => Some(filled.oid),
+            _ => None,
+        }
+    }
+
+    pub fn cloid(&self) -> Option<Cloid> {
+        match self {
+            OrderResponseStatus::Resting(resting) => resting.cloid,
+            OrderResponseStatus::Filled(filled) => filled.cloid,
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RestingOrder {
+    pub oid: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cloid: Option<Cloid>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FilledOrder {
+    pub total_sz: Sz,
+    pub avg_px: Px,
+    pub oid: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cloid: Option<Cloid>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CancelStatuses {
+    pub statuses: Vec<CancelResponseStatus>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum CancelResponseStatus {
+    Success,
+    Error(String),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TwapOrderStatus {
+    pub status: TwapResponseStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum TwapResponseStatus {
+    Running(RunningTwap),
+    Error(String),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningTwap {
+    pub twap_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TwapCancelStatus {
+    pub status: CancelResponseStatus,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_response_type() -> Result<()> {
+        let raw = r#"{"status":"ok","response":{"type":"somethingNew","data":{"id":7}}}"#;
+        let response: ExchangeResponse =
+            serde_json::from_str(raw).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let ExchangeResponseData::Unknown(data) = response.into_result()? else {
+            panic!("expected an unknown response");
+        };
+        assert_eq!(data["type"], "somethingNew");
+        assert_eq!(data["data"]["id"], 7);
+        Ok(())
+    }
+
+    #[test]
+    fn test_order_response_parsing() -> Result<()> {
+        let raw = r#"{"status":"ok","response":{"type":"order","data":{"statuses":[
+            {"resting":{"oid":77738308}},
+            {"filled":{"totalSz":"0.02","avgPx":"1891.4","oid":77747314,"cloid":"0x00000000000000000000000000000002"}},
+            {"error":"Order must have minimum value of $10."},
+            "waitingForTrigger"
+        ]}}}"#;
+        let mut response: ExchangeResponse =
+            serde_json::from_str(raw).map_err(|e| Error::JsonParse(e.to_string()))?;
+        response.link_cloids(vec![
+            Some(Cloid::from_bytes([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+            ])),
+            None,
+            None,
+            None,
+        ]);
+
+        let ExchangeResponseData::Order(statuses) = response.into_result()? else {
+            panic!("expected order statuses");
+        };
+        let outcomes: Vec<_> = statuses.outcomes().collect();
+        assert_eq!(outcomes.len(), 4);
+        assert_eq!(outcomes[0].status.oid(), Some(77738308));
+        assert_eq!(
+            outcomes[0].cloid.map(|cloid| cloid.to_hex()).as_deref(),
+            Some("0x00000000000000000000000000000001")
+        );
+        assert_eq!(outcomes[1].index, 1);
+        let OrderResponseStatus::Filled(filled) = outcomes[1].status else {
+            panic!("expected a fill");
+        };
+        assert_eq!(filled.total_sz, "0.02".parse()?);
+        assert_eq!(filled.avg_px, "1891.4".parse()?);
+        assert_eq!(
+            outcomes[1].cloid.map(|cloid| cloid.to_hex()).as_deref(),
+            Some("0x00000000000000000000000000000002")
+        );
+        assert!(matches!(outcomes[2].status, OrderResponseStatus::Error(_)));
+        assert!(matches!(
+            outcomes[3].status,
+            OrderResponseStatus::WaitingForTrigger
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cancel_default_and_err_parsing() -> Result<()> {
+        let cancel: ExchangeResponse = serde_json::from_str(
+            r#"{"status":"ok","response":{"type":"cancel","data":{"statuses":["success",{"error":"Order was never placed, already canceled, or filled."}]}}}"#,
+        )
+        .map_err(|e| Error::JsonParse(e.to_string()))?;
+        let ExchangeResponseData::Cancel(statuses) = cancel.into_result()? else {
+            panic!("expected cancel statuses");
+        };
+        assert!(matches!(
+            statuses.statuses[0],
+            CancelResponseStatus::Success
+        ));
+        assert!(matches!(
+            statuses.statuses[1],
+            CancelResponseStatus::Error(_)
+        ));
+
+        let default: ExchangeResponse =
+            serde_json::from_str(r#"{"status":"ok","response":{"type":"default"}}"#)
+                .map_err(|e| Error::JsonParse(e.to_string()))?;
+        assert!(matches!(
+            default.into_result()?,
+            ExchangeResponseData::Default
+        ));
+
+        let err: ExchangeResponse = serde_json::from_str(
+            r#"{"status":"err","response":"User or API Wallet does not exist."}"#,
+        )
+        .map_err(|e| Error::JsonParse(e.to_string()))?;
+        assert!(matches!(err.into_result(), Err(Error::Exchange(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_create_vault_response() -> Result<()> {
+        let address: Address = "0x1719884eb866cb12b2287399b15f7db5e7d775ea"
+            .parse()
+            .unwrap();
+
+        let vault: ExchangeResponse = serde_json::from_str(
+            r#"{"status":"ok","response":{"type":"createVault","data":"0x1719884eb866cb12b2287399b15f7db5e7d775ea"}}"#,
+        )
+        .map_err(|e| Error::JsonParse(e.to_string()))?;
+        assert!(matches!(
+            vault.into_result()?,
+            ExchangeResponseData::CreateVault(vault) if vault == address
+        ));
+
+        let sub_account: ExchangeResponse = serde_json::from_str(
+            r#"{"status":"ok","response":{"type":"createSubAccount","data":"0x1719884eb866cb12b2287399b15f7db5e7d775ea"}}"#,
+        )
+        .map_err(|e| Error::JsonParse(e.to_string()))?;
+        assert!(matches!(
+            sub_account.into_result()?,
+            ExchangeResponseData::CreateSubAccount(sub_account) if sub_account == address
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_twap_response_parsing() -> Result<()> {
+        let order: ExchangeResponse = serde_json::from_str(
+            r#"{"status":"ok","response":{"type":"twapOrder","data":{"status":{"running":{"twapId":77738308}}}}}"#,
+        )
+        .map_err(|e| Error::JsonParse(e.to_string()))?;
+        let ExchangeResponseData::TwapOrder(order) = order.into_result()? else {
+            panic!("expected twap order status");
+        };
+        assert!(matches!(
+            order.status,
+            TwapResponseStatus::Running(RunningTwap { twap_id: 77738308 })
+        ));
+
+        let cancel: ExchangeResponse = serde_json::from_str(
+            r#"{"status":"ok","response":{"type":"twapCancel","data":{"status":{"error":"TWAP was never placed, already canceled, or filled."}}}}"#,
+        )
+        .map_err(|e| Error::JsonParse(e.to_string()))?;
+        let ExchangeResponseData::TwapCancel(cancel) = cancel.into_result()? else {
+            panic!("expected twap cancel status");
+        };
+        assert!(matches!(cancel.status, CancelResponseStatus::Error(_)));
+        Ok(())
+    }
+}
@@ -0,0 +1,257 @@
+use alloy::primitives::{Address, Signature, B256, U256};
+use log::debug;
+use reqwest::Client;
+use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    exchange::{dtos::MessageResponse, hash_generator::Actions},
+    helpers::{BaseUrl, Chain},
+    prelude::*,
+    req::HttpClient,
+    signature::verify::verify_action,
+    Error, ExchangeResponse,
+};
+
+pub(crate) fn serialize_sig<S>(sig: &Signature, s: S) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut state = s.serialize_struct("Signature", 3)?;
+    state.serialize_field("r", &sig.r())?;
+    state.serialize_field("s", &sig.s())?;
+    state.serialize_field("v", &(27 + sig.v() as u64))?;
+    state.end()
+}
+
+pub(crate) fn deserialize_sig<'de, D>(d: D) -> std::result::Result<Signature, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Rsv {
+        r: U256,
+        s: U256,
+        v: u64,
+    }
+
+    let Rsv { r, s, v } = Rsv::deserialize(d)?;
+    let y_parity = match v {
+        0 | 27 => false,
+        1 | 28 => true,
+        v => {
+            return Err(serde::de::Error::custom(format!(
+                "invalid signature v: {v}"
+            )))
+        }
+    };
+    Ok(Signature::new(r, s, y_parity))
+}
+
+/// An action with its nonce and signature, in the shape the `/exchange` endpoint expects.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedAction {
+    pub action: Actions,
+    #[serde(serialize_with = "serialize_sig", deserialize_with = "deserialize_sig")]
+    pub signature: Signature,
+    pub nonce: u64,
+    pub vault_address: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_after: Option<u64>,
+}
+
+impl SignedAction {
+    pub fn new(
+        action: Actions,
+        signature: Signature,
+        nonce: u64,
+        vault_address: Option<Address>,
+    ) -> SignedAction {
+        SignedAction {
+            action,
+            signature,
+            nonce,
+            vault_address,
+            expires_after: None,
+        }
+    }
+
+    /// Pairs a [`MessageResponse`] from [`HashGenerator`] with the signature of its `message`,
+    /// keeping the nonce, vault and expiry it was hashed with.
+    ///
+    /// [`HashGenerator`]: crate::exchange::hash_generator::HashGenerator
+    pub fn from_message(message: MessageResponse, signature: Signature) -> SignedAction {
+        SignedAction {
+            action: message.action,
+            signature,
+            nonce: message.nonce,
+            vault_address: message.vault_address,
+            expires_after: message.expires_after,
+        }
+    }
+
+    /// Sets the expiry the action was signed with, see [`Actions::hash`].
+    pub fn with_expires_after(mut self, expires_after: u64) -> SignedAction {
+        self.expires_after = Some(expires_after);
+        self
+    }
+
+    /// The digest that was signed, see [`Actions::signing_hash`].
+    pub fn signing_hash(&self, chain: Chain) -> Result<B256> {
+        self.action
+            .signing_hash(self.nonce, self.vault_address, self.expires_after, chain)
+    }
+
+    /// Recovers the address that produced the signature, see [`verify_action`].
+    pub fn recover_signer(&self, chain: Chain) -> Result<Address> {
+        verify_action(
+            &self.action,
+            self.nonce,
+            self.vault_address,
+            self.expires_after,
+            &self.signature,
+            chain,
+        )
+    }
+
+    /// Fails with [`Error::SignerMismatch`] unless the signature recovers to `expected`.
+    pub fn verify(&self, expected: Address, chain: Chain) -> Result<()> {
+        let recovered = self.recover_signer(chain)?;
+        if recovered != expected {
+            return Err(Error::SignerMismatch {
+                expected,
+                recovered,
+            });
+        }
+        Ok(())
+    }
+}
+
+pub(crate) async fn post_signed(
+    http_client: &HttpClient,
+    signed: &SignedAction,
+) -> Result<ExchangeResponse> {
+    let res = serde_json::to_string(signed).map_err(|e| Error::JsonParse(e.to_string()))?;
+    debug!("Sending request {res:?}");
+
+    let output = http_client
+        .post("/exchange", res)
+        .await
+        .map_err(|e| Error::JsonParse(e.to_string()))?;
+    debug!("Response: {output}");
+    serde_json::from_str(&output).map_err(|e| Error::JsonParse(e.to_string()))
+}
+
+/// Posts actions signed outside of this process, e.g. hashes from
+/// [`HashGenerator`](crate::exchange::hash_generator::HashGenerator) signed by a custody
+/// service. Every action is checked against `signer` before it is sent.
+#[derive(Debug, Clone)]
+pub struct SignedActionClient {
+    pub http_client: HttpClient,
+    pub chain: Chain,
+    /// Address the signatures are expected to recover to: the account or one of its agents.
+    pub signer: Address,
+}
+
+impl SignedActionClient {
+    pub fn new(signer: Address, base_url: Option<BaseUrl>, client: Option<Client>) -> Self {
+        let base_url = base_url.unwrap_or(Chain::default().base_url());
+        SignedActionClient {
+            http_client: HttpClient {
+                client: client.unwrap_or_default(),
+                base_url: base_url.get_url(),
+            },
+            chain: base_url.chain(),
+            signer,
+        }
+    }
+
+    /// Posts a [`MessageResponse`] with the signature of its `message`.
+    pub async fn submit_signed(
+        &self,
+        message: MessageResponse,
+        signature: Signature,
+    ) -> Result<ExchangeResponse> {
+        self.submit(&SignedAction::from_message(message, signature))
+            .await
+    }
+
+    pub async fn submit(&self, signed: &SignedAction) -> Result<ExchangeResponse> {
+        signed.verify(self.signer, self.chain)?;
+        post_signed(&self.http_client, signed).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use alloy::signers::SignerSync;
+
+    use super::*;
+    use crate::{
+        exchange::{
+            actions::{ScheduleCancel, UsdSend},
+            hash_generator::HashGenerator,
+        },
+        nonce::NonceManager,
+        test_support::wallet,
+    };
+
+    #[test]
+    fn test_verify_signed_actions() -> Result<()> {
+        let wallet = wallet();
+        let chain = Chain::Testnet;
+        let actions = [
+            Actions::ScheduleCancel(ScheduleCancel { time: None }),
+            Actions::UsdSend(UsdSend {
+                signature_chain_id: chain.signature_chain_id(),
+                hyperliquid_chain: chain.hyperliquid_chain().to_string(),
+                destination: "0x0d1d9635d0640821d15e323ac8adadfa9c111414".to_string(),
+                amount: "1".to_string(),
+                time: 1690393044548,
+            }),
+        ];
+
+        for action in actions {
+            let hash = action.signing_hash(1690393044548, None, None, chain)?;
+            let signature = wallet.sign_hash_sync(&hash).unwrap();
+            let signed = SignedAction::new(action, signature, 1690393044548, None);
+            signed.verify(wallet.address(), chain)?;
+            assert!(matches!(
+                signed.verify(Address::ZERO, chain),
+                Err(Error::SignerMismatch { .. })
+            ));
+
+            let json = serde_json::to_string(&signed).unwrap();
+            let parsed: SignedAction = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed.signature, signed.signature);
+            assert_eq!(parsed.recover_signer(chain)?, wallet.address());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_signed_action_from_message() -> Result<()> {
+        let wallet = wallet();
+        let vault_address: Address = "0x0d1d9635d0640821d15e323ac8adadfa9c111414"
+            .parse()
+            .unwrap();
+        let generator = HashGenerator::new(Chain::Testnet)
+            .with_vault_address(vault_address)
+            .with_expires_after(1690393104548)
+            .with_nonce_manager(Arc::new(NonceManager::new()));
+
+        let message = generator.get_message_for_action(
+            Actions::ScheduleCancel(ScheduleCancel { time: None }),
+            Some(1690393044548),
+            None,
+        )?;
+        let signature = wallet.sign_hash_sync(&message.message).unwrap();
+        let signed = SignedAction::from_message(message, signature);
+        assert_eq!(signed.vault_address, Some(vault_address));
+        assert_eq!(signed.expires_after, Some(1690393104548));
+        signed.verify(wallet.address(), Chain::Testnet)?;
+        Ok(())
+    }
+}
@@ -1,31 +1,12 @@
-use std::sync::atomic::{AtomicU64, Ordering};
-
 use chrono::prelude::Utc;
-use lazy_static::lazy_static;
-use log::info;
-use uuid::Uuid;
 
 use crate::consts::*;
 
-fn now_timestamp_ms() -> u64 {
+pub(crate) fn now_timestamp_ms() -> u64 {
     let now = Utc::now();
     now.timestamp_millis() as u64
 }
 
-pub(crate) fn next_nonce() -> u64 {
-    let nonce = CUR_NONCE.fetch_add(1, Ordering::Relaxed);
-    let now_ms = now_timestamp_ms();
-    if nonce > now_ms + 1000 {
-        info!("nonce progressed too far ahead {nonce} {now_ms}");
-    }
-    // more than 300 seconds behind
-    if nonce + 300000 < now_ms {
-        CUR_NONCE.fetch_max(now_ms + 1, Ordering::Relaxed);
-        return now_ms;
-    }
-    nonce
-}
-
 pub(crate) const WIRE_DECIMALS: u8 = 8;
 
 pub(crate) fn float_to_string_for_hashing(x: f64) -> String {
@@ -43,25 +24,6 @@ pub(crate) fn float_to_string_for_hashing(x: f64) -> String {
     }
 }
 
-pub(crate) fn uuid_to_hex_string(uuid: Uuid) -> String {
-    let hex_string = uuid
-        .as_bytes()
-        .iter()
-        .map(|byte| format!("{byte:02x}"))
-        .collect::<Vec<String>>()
-        .join("");
-    format!("0x{hex_string}")
-}
-
-pub fn truncate_float(float: f64, decimals: u32, round_up: bool) -> f64 {
-    let pow10 = 10i64.pow(decimals) as f64;
-    let mut float = (float * pow10) as u64;
-    if round_up {
-        float += 1;
-    }
-    float as f64 / pow10
-}
-
 pub fn bps_diff(x: f64, y: f64) -> u16 {
     if x.abs() < EPSILON {
         INF_BPS
@@ -85,9 +47,66 @@ impl BaseUrl {
             BaseUrl::Testnet => TESTNET_API_URL.to_string(),
         }
     }
+
+    /// The chain whose signing parameters match this API. A local node is treated as testnet.
+    pub fn chain(&self) -> Chain {
+        match self {
+            BaseUrl::Mainnet => Chain::Mainnet,
+            BaseUrl::Testnet | BaseUrl::Localhost => Chain::Testnet,
+        }
+    }
 }
-lazy_static! {
-    static ref CUR_NONCE: AtomicU64 = AtomicU64::new(now_timestamp_ms());
+
+/// The network a client signs for. It selects the L1 agent source, the user-signed
+/// `hyperliquidChain` name and the signature chain id, so mainnet and testnet clients can
+/// live in the same process. The `testnet` feature only changes the default.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Chain {
+    Mainnet,
+    Testnet,
+}
+
+impl Default for Chain {
+    fn default() -> Self {
+        if cfg!(feature = "testnet") {
+            Chain::Testnet
+        } else {
+            Chain::Mainnet
+        }
+    }
+}
+
+impl Chain {
+    pub fn base_url(&self) -> BaseUrl {
+        match self {
+            Chain::Mainnet => BaseUrl::Mainnet,
+            Chain::Testnet => BaseUrl::Testnet,
+        }
+    }
+
+    /// `source` field of the L1 agent payload.
+    pub fn source(&self) -> &'static str {
+        match self {
+            Chain::Mainnet => "a",
+            Chain::Testnet => "b",
+        }
+    }
+
+    /// `hyperliquidChain` field of user-signed actions.
+    pub fn hyperliquid_chain(&self) -> &'static str {
+        match self {
+            Chain::Mainnet => "Mainnet",
+            Chain::Testnet => "Testnet",
+        }
+    }
+
+    /// `signatureChainId` of user-signed actions.
+    pub fn signature_chain_id(&self) -> u64 {
+        match self {
+            Chain::Mainnet => 999,
+            Chain::Testnet => 998,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -134,4 +153,27 @@ mod tests {
             "987654321".to_string()
         );
     }
+
+    #[test]
+    fn chain_mapping_test() {
+        assert_eq!(Chain::Mainnet.source(), "a");
+        assert_eq!(Chain::Mainnet.hyperliquid_chain(), "Mainnet");
+        assert_eq!(Chain::Mainnet.signature_chain_id(), 999);
+        assert_eq!(Chain::Testnet.source(), "b");
+        assert_eq!(Chain::Testnet.hyperliquid_chain(), "Testnet");
+        assert_eq!(Chain::Testnet.signature_chain_id(), 998);
+
+        assert_eq!(BaseUrl::Mainnet.chain(), Chain::Mainnet);
+        assert_eq!(BaseUrl::Testnet.chain(), Chain::Testnet);
+        assert_eq!(BaseUrl::Localhost.chain(), Chain::Testnet);
+        assert_eq!(Chain::Mainnet.base_url().get_url(), MAINNET_API_URL);
+        assert_eq!(Chain::Testnet.base_url().get_url(), TESTNET_API_URL);
+
+        let expected = if cfg!(feature = "testnet") {
+            Chain::Testnet
+        } else {
+            Chain::Mainnet
+        };
+        assert_eq!(Chain::default(), expected);
+    }
 }
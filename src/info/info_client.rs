@@ -6,9 +6,21 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::{
-    errors::Error, helpers::BaseUrl, info::{
-        CandlesSnapshotResponse, FundingHistoryResponse, L2SnapshotResponse, OpenOrdersResponse, OrderInfo, OrderStatusResponse, RecentTradesResponse, ReferralResponse, UserFeesResponse, UserFillsResponse, UserFundingResponse, UserStateResponse, UserTokenBalanceResponse
-    }, meta::{AssetContext, Meta, SpotMeta, SpotMetaAndAssetCtxs}, prelude::*, req::HttpClient, ws::{Message, Subscription, WsManager}
+    assets::AssetResolver,
+    errors::Error,
+    helpers::{BaseUrl, Chain},
+    info::{
+        CandlesSnapshotResponse, DelegationsResponse, DelegatorHistoryResponse,
+        DelegatorRewardsResponse, DelegatorSummaryResponse, ExtraAgentsResponse,
+        FundingHistoryResponse, L2SnapshotResponse, OpenOrdersResponse, OrderInfo,
+        OrderStatusResponse, PerpDex, RecentTradesResponse, ReferralResponse, SubAccountsResponse,
+        UserFeesResponse, UserFillsResponse, UserFundingResponse, UserStateResponse,
+        UserTokenBalanceResponse, UserVaultEquitiesResponse, VaultDetailsResponse,
+    },
+    meta::{AssetContext, Meta, SpotMeta, SpotMetaAndAssetCtxs},
+    prelude::*,
+    req::HttpClient,
+    ws::{Message, Subscription, TwapSliceFill, WsManager},
 };
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -27,6 +39,8 @@ pub enum InfoRequest {
     #[serde(rename = "clearinghouseState")]
     UserState {
         user: Address,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        dex: Option<String>,
     },
     #[serde(rename = "batchClearinghouseStates")]
     UserStates {
@@ -46,11 +60,17 @@ pub enum InfoRequest {
         user: Address,
         oid: u64,
     },
-    Meta,
+    Meta {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        dex: Option<String>,
+    },
     MetaAndAssetCtxs,
     SpotMeta,
     SpotMetaAndAssetCtxs,
-    AllMids,
+    AllMids {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        dex: Option<String>,
+    },
     UserFills {
         user: Address,
     },
@@ -82,6 +102,37 @@ pub enum InfoRequest {
     HistoricalOrders {
         user: Address,
     },
+    ExtraAgents {
+        user: Address,
+    },
+    SubAccounts {
+        user: Address,
+    },
+    #[serde(rename_all = "camelCase")]
+    VaultDetails {
+        vault_address: Address,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        user: Option<Address>,
+    },
+    UserVaultEquities {
+        user: Address,
+    },
+    UserTwapSliceFills {
+        user: Address,
+    },
+    PerpDexs,
+    Delegations {
+        user: Address,
+    },
+    DelegatorSummary {
+        user: Address,
+    },
+    DelegatorHistory {
+        user: Address,
+    },
+    DelegatorRewards {
+        user: Address,
+    },
 }
 
 #[derive(Debug)]
@@ -103,13 +154,21 @@ impl InfoClient {
         Self::new_internal(client, base_url, true).await
     }
 
+    pub(crate) fn from_http_client(http_client: HttpClient) -> InfoClient {
+        InfoClient {
+            http_client,
+            ws_manager: None,
+            reconnect: false,
+        }
+    }
+
     async fn new_internal(
         client: Option<Client>,
         base_url: Option<BaseUrl>,
         reconnect: bool,
     ) -> Result<InfoClient> {
         let client = client.unwrap_or_default();
-        let base_url = base_url.unwrap_or(BaseUrl::Mainnet).get_url();
+        let base_url = base_url.unwrap_or(Chain::default().base_url()).get_url();
 
         Ok(InfoClient {
             http_client: HttpClient { client, base_url },
@@ -176,7 +235,23 @@ impl InfoClient {
     }
 
     pub async fn user_state(&self, address: Address) -> Result<UserStateResponse> {
-        let input = InfoRequest::UserState { user: address };
+        let input = InfoRequest::UserState {
+            user: address,
+            dex: None,
+        };
+        self.send_info_request(input).await
+    }
+
+    /// `address`'s positions and margin on the builder-deployed `dex`.
+    pub async fn user_state_for_dex(
+        &self,
+        address: Address,
+        dex: &str,
+    ) -> Result<UserStateResponse> {
+        let input = InfoRequest::UserState {
+            user: address,
+            dex: Some(dex.to_string()),
+        };
         self.send_info_request(input).await
     }
 
@@ -196,7 +271,22 @@ impl InfoClient {
     }
 
     pub async fn meta(&self) -> Result<Meta> {
-        let input = InfoRequest::Meta;
+        let input = InfoRequest::Meta { dex: None };
+        self.send_info_request(input).await
+    }
+
+    /// Perps of the builder-deployed `dex`.
+    pub async fn meta_for_dex(&self, dex: &str) -> Result<Meta> {
+        let input = InfoRequest::Meta {
+            dex: Some(dex.to_string()),
+        };
+        self.send_info_request(input).await
+    }
+
+    /// The perp dexes, indexed as [`AssetResolver::add_perp_dex`] expects. The default dex at
+    /// index 0 is `None`.
+    pub async fn perp_dexs(&self) -> Result<Vec<Option<PerpDex>>> {
+        let input = InfoRequest::PerpDexs;
         self.send_info_request(input).await
     }
 
@@ -210,13 +300,42 @@ impl InfoClient {
         self.send_info_request(input).await
     }
 
+    /// Builds an [`AssetResolver`] covering the default perp dex and all spot pairs.
+    pub async fn asset_resolver(&self) -> Result<AssetResolver> {
+        let meta = self.meta().await?;
+        let spot_meta = self.spot_meta().await?;
+        let mut resolver = AssetResolver::new(&meta, Some(&spot_meta));
+        self.add_perp_dexs(&mut resolver).await?;
+        Ok(resolver)
+    }
+
+    /// Registers the perps of every builder-deployed dex with `resolver`.
+    pub async fn add_perp_dexs(&self, resolver: &mut AssetResolver) -> Result<()> {
+        for (index, dex) in self.perp_dexs().await?.into_iter().enumerate() {
+            let Some(dex) = dex else {
+                continue;
+            };
+            let meta = self.meta_for_dex(&dex.name).await?;
+            resolver.add_perp_dex(index as u32, &dex.name, &meta);
+        }
+        Ok(())
+    }
+
     pub async fn spot_meta_and_asset_contexts(&self) -> Result<Vec<SpotMetaAndAssetCtxs>> {
         let input = InfoRequest::SpotMetaAndAssetCtxs;
         self.send_info_request(input).await
     }
 
     pub async fn all_mids(&self) -> Result<HashMap<String, String>> {
-        let input = InfoRequest::AllMids;
+        let input = InfoRequest::AllMids { dex: None };
+        self.send_info_request(input).await
+    }
+
+    /// Mid prices of the perps on the builder-deployed `dex`.
+    pub async fn all_mids_for_dex(&self, dex: &str) -> Result<HashMap<String, String>> {
+        let input = InfoRequest::AllMids {
+            dex: Some(dex.to_string()),
+        };
         self.send_info_request(input).await
     }
 
@@ -299,4 +418,76 @@ impl InfoClient {
         let input = InfoRequest::HistoricalOrders { user: address };
         self.send_info_request(input).await
     }
+
+    /// Lists the API wallets approved by `address`.
+    pub async fn extra_agents(&self, address: Address) -> Result<Vec<ExtraAgentsResponse>> {
+        let input = InfoRequest::ExtraAgents { user: address };
+        self.send_info_request(input).await
+    }
+
+    /// Lists the sub-accounts of `address` with their perp and spot state.
+    pub async fn sub_accounts(&self, address: Address) -> Result<Vec<SubAccountsResponse>> {
+        let input = InfoRequest::SubAccounts { user: address };
+        // The exchange answers `null` for users without sub-accounts.
+        let sub_accounts: Option<Vec<SubAccountsResponse>> = self.send_info_request(input).await?;
+        Ok(sub_accounts.unwrap_or_default())
+    }
+
+    /// Details of `vault`, including `user`'s position in it if given.
+    pub async fn vault_details(
+        &self,
+        vault: Address,
+        user: Option<Address>,
+    ) -> Result<VaultDetailsResponse> {
+        let input = InfoRequest::VaultDetails {
+            vault_address: vault,
+            user,
+        };
+        self.send_info_request(input).await
+    }
+
+    /// The vaults `address` has deposited into and its equity in each.
+    pub async fn user_vault_equities(
+        &self,
+        address: Address,
+    ) -> Result<Vec<UserVaultEquitiesResponse>> {
+        let input = InfoRequest::UserVaultEquities { user: address };
+        self.send_info_request(input).await
+    }
+
+    /// The most recent fills of `address`'s TWAP slices, with the TWAP each belongs to.
+    pub async fn user_twap_slice_fills(&self, address: Address) -> Result<Vec<TwapSliceFill>> {
+        let input = InfoRequest::UserTwapSliceFills { user: address };
+        self.send_info_request(input).await
+    }
+
+    /// The validators `address` has delegated HYPE to.
+    pub async fn delegations(&self, address: Address) -> Result<Vec<DelegationsResponse>> {
+        let input = InfoRequest::Delegations { user: address };
+        self.send_info_request(input).await
+    }
+
+    /// Totals of `address`'s staking balance.
+    pub async fn delegator_summary(&self, address: Address) -> Result<DelegatorSummaryResponse> {
+        let input = InfoRequest::DelegatorSummary { user: address };
+        self.send_info_request(input).await
+    }
+
+    /// Delegations, undelegations, deposits and withdrawals of `address`'s staking balance.
+    pub async fn delegator_history(
+        &self,
+        address: Address,
+    ) -> Result<Vec<DelegatorHistoryResponse>> {
+        let input = InfoRequest::DelegatorHistory { user: address };
+        self.send_info_request(input).await
+    }
+
+    /// Staking rewards paid to `address`.
+    pub async fn delegator_rewards(
+        &self,
+        address: Address,
+    ) -> Result<Vec<DelegatorRewardsResponse>> {
+        let input = InfoRequest::DelegatorRewards { user: address };
+        self.send_info_request(input).await
+    }
 }
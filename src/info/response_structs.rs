@@ -1,7 +1,16 @@
+use alloy::primitives::Address;
 use serde::Deserialize;
 
 use crate::{
-    info::{AssetPosition, DailyUserVlm, Delta, FeeSchedule, Level, MarginSummary, OrderInfo, Referrer, ReferrerState, UserTokenBalance},
+    cloid::Cloid,
+    decimal::{Px, Sz},
+    enums::Side,
+    info::{
+        AssetPosition, DailyUserVlm, DelegatorDelta, Delta, FeeSchedule, Level, MarginSummary,
+        OrderInfo, Referrer, ReferrerState, UserTokenBalance, VaultFollower, VaultPortfolio,
+        VaultRelationship,
+    },
+    prelude::*,
 };
 
 #[derive(Deserialize, Debug)]
@@ -34,10 +43,21 @@ pub struct OpenOrdersResponse {
     pub coin: String,
     pub limit_px: String,
     pub oid: u64,
-    pub side: String,
+    pub side: Side,
     pub sz: String,
     pub timestamp: u64,
-    pub cloid: Option<String>,
+    pub cloid: Option<Cloid>,
+}
+
+impl OpenOrdersResponse {
+    pub fn limit_price(&self) -> Result<Px> {
+        self.limit_px.parse()
+    }
+
+    /// Remaining size of the order.
+    pub fn size(&self) -> Result<Sz> {
+        self.sz.parse()
+    }
 }
 
 #[derive(serde::Deserialize, Debug)]
@@ -50,7 +70,7 @@ pub struct UserFillsResponse {
     pub hash: String,
     pub oid: u64,
     pub px: String,
-    pub side: String,
+    pub side: Side,
     pub start_position: String,
     pub sz: String,
     pub time: u64,
@@ -85,7 +105,7 @@ pub struct L2SnapshotResponse {
 #[serde(rename_all = "camelCase")]
 pub struct RecentTradesResponse {
     pub coin: String,
-    pub side: String,
+    pub side: Side,
     pub px: String,
     pub sz: String,
     pub time: u64,
@@ -132,4 +152,102 @@ pub struct ReferralResponse {
     pub unclaimed_rewards: String,
     pub claimed_rewards: String,
     pub referrer_state: ReferrerState,
-}
\ No newline at end of file
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtraAgentsResponse {
+    pub name: String,
+    pub address: Address,
+    pub valid_until: u64,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountsResponse {
+    pub name: String,
+    pub sub_account_user: Address,
+    pub master: Address,
+    pub clearinghouse_state: UserStateResponse,
+    pub spot_state: UserTokenBalanceResponse,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultDetailsResponse {
+    pub name: String,
+    pub vault_address: Address,
+    pub leader: Address,
+    pub description: String,
+    /// Account value and PnL history per period (`"day"`, `"week"`, `"month"`, `"allTime"`,
+    /// ...).
+    pub portfolio: Vec<(String, VaultPortfolio)>,
+    pub apr: f64,
+    /// The queried user's position in the vault, if a user was given and follows it.
+    pub follower_state: Option<VaultFollower>,
+    /// Share of the vault's equity owned by the leader.
+    pub leader_fraction: f64,
+    /// Share of follower profits paid to the leader.
+    pub leader_commission: f64,
+    pub followers: Vec<VaultFollower>,
+    pub max_distributable: f64,
+    pub max_withdrawable: f64,
+    pub is_closed: bool,
+    pub relationship: VaultRelationship,
+    pub allow_deposits: bool,
+    pub always_close_on_withdraw: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserVaultEquitiesResponse {
+    pub vault_address: Address,
+    pub equity: String,
+    #[serde(default)]
+    pub locked_until_timestamp: Option<u64>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegationsResponse {
+    pub validator: Address,
+    pub amount: String,
+    pub locked_until_timestamp: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatorSummaryResponse {
+    pub delegated: String,
+    pub undelegated: String,
+    pub total_pending_withdrawal: String,
+    pub n_pending_withdrawals: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatorHistoryResponse {
+    pub time: u64,
+    pub hash: String,
+    pub delta: DelegatorDelta,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatorRewardsResponse {
+    pub time: u64,
+    /// `"delegation"` or `"commission"`.
+    pub source: String,
+    pub total_amount: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpDex {
+    pub name: String,
+    pub full_name: String,
+    pub deployer: Address,
+    pub oracle_updater: Option<Address>,
+    #[serde(default)]
+    pub fee_recipient: Option<Address>,
+}
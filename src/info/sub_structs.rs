@@ -1,6 +1,13 @@
 use alloy::primitives::Address;
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    cloid::Cloid,
+    decimal::{Px, Sz},
+    enums::{OrderStatus, Side, Tif},
+    prelude::*,
+};
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Leverage {
@@ -34,6 +41,21 @@ pub struct PositionData {
     pub cum_funding: CumulativeFunding,
 }
 
+impl PositionData {
+    /// Signed position size, negative for shorts.
+    pub fn size(&self) -> Result<Sz> {
+        self.szi.parse()
+    }
+
+    pub fn entry_price(&self) -> Result<Option<Px>> {
+        self.entry_px.as_deref().map(str::parse).transpose()
+    }
+
+    pub fn liquidation_price(&self) -> Result<Option<Px>> {
+        self.liquidation_px.as_deref().map(str::parse).transpose()
+    }
+}
+
 #[derive(Deserialize, Debug)]
 pub struct AssetPosition {
     pub position: PositionData,
@@ -58,6 +80,16 @@ pub struct Level {
     pub sz: String,
 }
 
+impl Level {
+    pub fn price(&self) -> Result<Px> {
+        self.px.parse()
+    }
+
+    pub fn size(&self) -> Result<Sz> {
+        self.sz.parse()
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Delta {
@@ -121,7 +153,7 @@ pub struct UserTokenBalance {
 #[serde(rename_all = "camelCase")]
 pub struct OrderInfo {
     pub order: BasicOrderInfo,
-    pub status: String,
+    pub status: OrderStatus,
     pub status_timestamp: u64,
 }
 
@@ -129,7 +161,7 @@ pub struct OrderInfo {
 #[serde(rename_all = "camelCase")]
 pub struct BasicOrderInfo {
     pub coin: String,
-    pub side: String,
+    pub side: Side,
     pub limit_px: String,
     pub sz: String,
     pub oid: u64,
@@ -141,8 +173,8 @@ pub struct BasicOrderInfo {
     pub reduce_only: bool,
     pub order_type: String,
     pub orig_sz: String,
-    pub tif: Option<String>,
-    pub cloid: Option<String>,
+    pub tif: Option<Tif>,
+    pub cloid: Option<Cloid>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -164,3 +196,52 @@ pub struct ReferrerState {
 pub struct ReferrerData {
     pub required: String,
 }
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultFollower {
+    pub user: Address,
+    pub vault_equity: String,
+    pub pnl: String,
+    pub all_time_pnl: String,
+    pub days_following: u64,
+    pub vault_entry_time: u64,
+    /// Time in milliseconds until which the follower cannot withdraw.
+    pub lockup_until: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultPortfolio {
+    /// `(time, value)` pairs.
+    pub account_value_history: Vec<(u64, String)>,
+    /// `(time, pnl)` pairs.
+    pub pnl_history: Vec<(u64, String)>,
+    pub vlm: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct VaultRelationship {
+    /// `"normal"`, `"parent"` or `"child"`.
+    #[serde(rename = "type")]
+    pub type_string: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum DelegatorDelta {
+    Delegate {
+        validator: Address,
+        amount: String,
+        #[serde(rename = "isUndelegate")]
+        is_undelegate: bool,
+    },
+    CDeposit {
+        amount: String,
+    },
+    Withdrawal {
+        amount: String,
+        /// `"initiated"` or `"finalized"`.
+        phase: String,
+    },
+}
@@ -1,28 +1,37 @@
 #![deny(unreachable_pub)]
+pub mod assets;
+pub mod cloid;
 pub mod consts;
+pub mod decimal;
 pub mod eip712;
+pub mod enums;
 pub mod errors;
 pub mod exchange;
 pub mod helpers;
 pub mod info;
 pub mod meta;
+pub mod nonce;
 pub mod prelude;
 pub mod req;
+pub mod rules;
 pub mod signature;
+#[cfg(test)]
+mod test_support;
 pub mod ws;
 
 // Re-exports for convenience
+pub use assets::{AssetInfo, AssetResolver};
+pub use cloid::Cloid;
+pub use decimal::{Px, Sz};
+pub use enums::{OrderStatus, Side, Tif, TpSl};
 pub use errors::Error;
+pub use exchange::dead_mans_switch::DeadMansSwitch;
 pub use exchange::exchange_client::ExchangeClient;
+pub use exchange::response::{ExchangeResponse, ExchangeResponseData};
+pub use exchange::signed_action::{SignedAction, SignedActionClient};
 pub use helpers::BaseUrl;
 pub use info::info_client::InfoClient;
+pub use nonce::NonceManager;
 pub use req::HttpClient;
-
-// Common types
-use serde::{Deserialize, Serialize};
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct ExchangeResponseStatus {
-    pub status: String,
-    pub response: Option<serde_json::Value>,
-}
+pub use rules::{AssetRule, Rounding};
+pub use signature::signer::{HyperliquidSigner, RemoteSigner};
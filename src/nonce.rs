@@ -0,0 +1,226 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use alloy::primitives::Address;
+use lazy_static::lazy_static;
+use log::info;
+
+use crate::{errors::Error, helpers::now_timestamp_ms, prelude::*};
+
+/// How far ahead of the issued nonces the persisted high-water mark is reserved, so the file
+/// is written once per window instead of once per nonce.
+const PERSIST_RESERVE_MS: u64 = 10_000;
+
+/// Writes `data` to a temporary file next to `path` and renames it over `path`, so a crash
+/// leaves either the old or the new marks, never a truncated file.
+fn write_atomically(path: &Path, data: &str) -> Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    let mut file = File::create(&tmp).map_err(|e| Error::Nonce(e.to_string()))?;
+    file.write_all(data.as_bytes())
+        .and_then(|()| file.sync_all())
+        .map_err(|e| Error::Nonce(e.to_string()))?;
+    fs::rename(&tmp, path).map_err(|e| Error::Nonce(e.to_string()))
+}
+
+lazy_static! {
+    static ref GLOBAL: Arc<NonceManager> = Arc::new(NonceManager::new());
+}
+
+#[derive(Debug, Default)]
+struct Signers {
+    /// Highest nonce issued per signer.
+    issued: HashMap<Address, u64>,
+    /// Highest nonce per signer that is safe to issue without writing the file again.
+    reserved: HashMap<Address, u64>,
+    /// Highest nonce issued without a known signer. It is not persisted.
+    shared: u64,
+}
+
+/// Issues nonces per signer. Hyperliquid keeps the 100 highest nonces of every signer and
+/// rejects anything lower or reused, so nonces are the (server-adjusted) time in milliseconds,
+/// bumped past the last issued one when several are taken within the same millisecond.
+///
+/// The clients never adjust the clock themselves: until [`NonceManager::set_server_time`] is
+/// called, nonces follow the local clock.
+#[derive(Debug, Default)]
+pub struct NonceManager {
+    signers: Mutex<Signers>,
+    /// Server time minus local time, in milliseconds.
+    clock_offset_ms: AtomicI64,
+    /// File the high-water marks are persisted to, if any.
+    path: Option<PathBuf>,
+}
+
+impl NonceManager {
+    pub fn new() -> NonceManager {
+        NonceManager::default()
+    }
+
+    /// A manager that persists its high-water marks to `path`, loading them if the file
+    /// exists, so a restarted process never reuses a nonce.
+    pub fn with_persistence(path: impl Into<PathBuf>) -> Result<NonceManager> {
+        let path = path.into();
+        let mut signers = Signers::default();
+        if path.exists() {
+            let data = fs::read_to_string(&path).map_err(|e| Error::Nonce(e.to_string()))?;
+            let marks: HashMap<Address, u64> =
+                serde_json::from_str(&data).map_err(|e| Error::JsonParse(e.to_string()))?;
+            signers.issued = marks.clone();
+            signers.reserved = marks;
+        }
+
+        Ok(NonceManager {
+            signers: Mutex::new(signers),
+            clock_offset_ms: AtomicI64::new(0),
+            path: Some(path),
+        })
+    }
+
+    /// The process-wide manager shared by clients that were not given their own.
+    pub fn global() -> Arc<NonceManager> {
+        Arc::clone(&GLOBAL)
+    }
+
+    /// Records the server's current time, so nonces follow the server clock rather than a
+    /// skewed local one. Nothing calls this for you; on hosts whose clock may drift, call it
+    /// periodically with a server timestamp, such as the `time` of a trade from
+    /// [`InfoClient::recent_trades`](crate::InfoClient::recent_trades) or a websocket message.
+    pub fn set_server_time(&self, server_time_ms: u64) {
+        let offset = server_time_ms as i64 - now_timestamp_ms() as i64;
+        self.clock_offset_ms.store(offset, Ordering::Relaxed);
+    }
+
+    pub fn clock_offset_ms(&self) -> i64 {
+        self.clock_offset_ms.load(Ordering::Relaxed)
+    }
+
+    /// Current time in milliseconds, adjusted by the server clock offset.
+    pub fn now(&self) -> u64 {
+        now_timestamp_ms().saturating_add_signed(self.clock_offset_ms())
+    }
+
+    /// The next nonce for `signer`: the current time, or one more than the last nonce issued
+    /// to it if that is not in the past.
+    pub fn next(&self, signer: Address) -> Result<u64> {
+        let now = self.now();
+        let mut signers = self
+            .signers
+            .lock()
+            .map_err(|e| Error::Nonce(e.to_string()))?;
+
+        let last = signers.issued.get(&signer).copied().unwrap_or_default();
+        let nonce = now.max(last.max(signers.shared) + 1);
+        if nonce > now + 1000 {
+            info!("nonce for {signer} progressed too far ahead {nonce} {now}");
+        }
+
+        if let Some(path) = &self.path {
+            if signers
+                .reserved
+                .get(&signer)
+                .is_none_or(|&mark| nonce > mark)
+            {
+                let mut marks = signers.reserved.clone();
+                marks.insert(signer, nonce + PERSIST_RESERVE_MS);
+                let data =
+                    serde_json::to_string(&marks).map_err(|e| Error::JsonParse(e.to_string()))?;
+                write_atomically(path, &data)?;
+                signers.reserved = marks;
+            }
+        }
+
+        signers.issued.insert(signer, nonce);
+        Ok(nonce)
+    }
+
+    /// The next nonce for a signer that is not known yet. It is above every nonce issued so
+    /// far, to any signer, and every later nonce is above it, so it cannot collide with
+    /// whichever signer ends up using it.
+    pub fn next_shared(&self) -> Result<u64> {
+        let now = self.now();
+        let mut signers = self
+            .signers
+            .lock()
+            .map_err(|e| Error::Nonce(e.to_string()))?;
+
+        let last = signers
+            .issued
+            .values()
+            .copied()
+            .fold(signers.shared, u64::max);
+        let nonce = now.max(last + 1);
+        signers.shared = nonce;
+        Ok(nonce)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_concurrent_nonces_are_unique() -> Result<()> {
+        let manager = Arc::new(NonceManager::new());
+        let signer = Address::repeat_byte(1);
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let manager = Arc::clone(&manager);
+                std::thread::spawn(move || {
+                    (0..100)
+                        .map(|_| manager.next(signer).unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let nonces: HashSet<u64> = handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect();
+        assert_eq!(nonces.len(), 400);
+        Ok(())
+    }
+
+    #[test]
+    fn test_shared_nonces_never_collide() -> Result<()> {
+        let manager = NonceManager::new();
+        let signer = Address::repeat_byte(1);
+
+        let first = manager.next(signer)?;
+        let shared = manager.next_shared()?;
+        assert!(shared > first);
+        assert!(manager.next(Address::repeat_byte(2))? > shared);
+        assert!(manager.next(signer)? > shared);
+        Ok(())
+    }
+
+    #[test]
+    fn test_persisted_nonces_survive_restart() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("hl-nonces-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let signer = Address::repeat_byte(1);
+
+        let manager = NonceManager::with_persistence(&path)?;
+        // A clock running an hour ahead, as if it was set back after a restart.
+        manager.set_server_time(now_timestamp_ms() + 3_600_000);
+        let before = manager.next(signer)?;
+
+        let restarted = NonceManager::with_persistence(&path)?;
+        assert!(restarted.next(signer)? > before);
+        assert!(!path.with_extension("json.tmp").exists());
+        fs::remove_file(&path).unwrap();
+        Ok(())
+    }
+}
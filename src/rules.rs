@@ -0,0 +1,163 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::{
+    decimal::{Px, Sz},
+    errors::Error,
+    prelude::*,
+};
+
+const MAX_SIGNIFICANT_FIGURES: u32 = 5;
+const MAX_PERP_PX_DECIMALS: u32 = 6;
+const MAX_SPOT_PX_DECIMALS: u32 = 8;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// Towards positive infinity.
+    Up,
+    /// Towards negative infinity.
+    Down,
+    /// To the nearest valid value, ties away from zero.
+    Nearest,
+}
+
+impl Rounding {
+    fn strategy(self) -> RoundingStrategy {
+        match self {
+            Rounding::Up => RoundingStrategy::ToPositiveInfinity,
+            Rounding::Down => RoundingStrategy::ToNegativeInfinity,
+            Rounding::Nearest => RoundingStrategy::MidpointAwayFromZero,
+        }
+    }
+}
+
+/// Tick and lot rules of a single asset.
+///
+/// Prices may have at most 5 significant figures (integer prices are always allowed) and at
+/// most `6 - sz_decimals` decimals for perps or `8 - sz_decimals` for spot. Sizes may have at
+/// most `sz_decimals` decimals.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AssetRule {
+    pub sz_decimals: u32,
+    pub is_spot: bool,
+}
+
+impl AssetRule {
+    pub fn max_px_decimals(&self) -> u32 {
+        let max_decimals = if self.is_spot {
+            MAX_SPOT_PX_DECIMALS
+        } else {
+            MAX_PERP_PX_DECIMALS
+        };
+        max_decimals.saturating_sub(self.sz_decimals)
+    }
+
+    /// Decimals allowed for `px` once the significant figure limit is applied.
+    fn px_decimals(&self, px: Decimal) -> u32 {
+        let px = px.abs();
+        if px.is_zero() {
+            return self.max_px_decimals();
+        }
+        let mut exponent = 0i64;
+        let mut scaled = px;
+        while scaled >= Decimal::TEN {
+            scaled /= Decimal::TEN;
+            exponent += 1;
+        }
+        while scaled < Decimal::ONE {
+            scaled *= Decimal::TEN;
+            exponent -= 1;
+        }
+        let sig_fig_decimals = (MAX_SIGNIFICANT_FIGURES as i64 - 1 - exponent).max(0) as u32;
+        sig_fig_decimals.min(self.max_px_decimals())
+    }
+
+    pub fn round_px(&self, px: Px, rounding: Rounding) -> Px {
+        let value = px.value();
+        let rounded = value.round_dp_with_strategy(self.px_decimals(value), rounding.strategy());
+        Px::new(rounded.normalize())
+    }
+
+    pub fn round_sz(&self, sz: Sz, rounding: Rounding) -> Sz {
+        let rounded = sz
+            .value()
+            .round_dp_with_strategy(self.sz_decimals, rounding.strategy());
+        Sz::new(rounded.normalize())
+    }
+
+    pub fn validate_px(&self, px: Px) -> Result<()> {
+        if self.round_px(px, Rounding::Down) != Px::new(px.value().normalize()) {
+            return Err(Error::InvalidOrder(format!(
+                "price {px} has more than {MAX_SIGNIFICANT_FIGURES} significant figures or more than {} decimals",
+                self.max_px_decimals()
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn validate_sz(&self, sz: Sz) -> Result<()> {
+        if self.round_sz(sz, Rounding::Down) != Sz::new(sz.value().normalize()) {
+            return Err(Error::InvalidOrder(format!(
+                "size {sz} has more than {} decimals",
+                self.sz_decimals
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn px(value: &str) -> Px {
+        value.parse().unwrap()
+    }
+
+    fn sz(value: &str) -> Sz {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_perp_price_rounding() {
+        let rule = AssetRule {
+            sz_decimals: 2,
+            is_spot: false,
+        };
+        assert_eq!(rule.max_px_decimals(), 4);
+        assert_eq!(
+            rule.round_px(px("1234.567"), Rounding::Nearest),
+            px("1234.6")
+        );
+        assert_eq!(rule.round_px(px("1234.567"), Rounding::Down), px("1234.5"));
+        assert_eq!(rule.round_px(px("1234.51"), Rounding::Up), px("1234.6"));
+        assert_eq!(
+            rule.round_px(px("123456.7"), Rounding::Nearest),
+            px("123457")
+        );
+        assert_eq!(
+            rule.round_px(px("0.0123456"), Rounding::Nearest),
+            px("0.0123")
+        );
+        assert!(rule.validate_px(px("123456")).is_ok());
+        assert!(rule.validate_px(px("1234.5")).is_ok());
+        assert!(rule.validate_px(px("1234.56")).is_err());
+        assert!(rule.validate_px(px("0.12345")).is_err());
+    }
+
+    #[test]
+    fn test_spot_price_and_size_rounding() {
+        let rule = AssetRule {
+            sz_decimals: 0,
+            is_spot: true,
+        };
+        assert_eq!(rule.max_px_decimals(), 8);
+        assert_eq!(
+            rule.round_px(px("0.000123456"), Rounding::Nearest),
+            px("0.00012346")
+        );
+        assert_eq!(rule.round_sz(sz("10.7"), Rounding::Down), sz("10"));
+        assert_eq!(rule.round_sz(sz("-10.2"), Rounding::Down), sz("-11"));
+        assert!(rule.validate_sz(sz("3")).is_ok());
+        assert!(rule.validate_sz(sz("3.5")).is_err());
+    }
+}
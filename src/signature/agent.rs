@@ -6,6 +6,8 @@ pub(crate) mod l1 {
         sol_types::{eip712_domain, SolStruct},
     };
 
+    use serde_json::{json, Value};
+
     use crate::eip712::Eip712;
 
     sol! {
@@ -28,5 +30,16 @@ pub(crate) mod l1 {
         fn struct_hash(&self) -> B256 {
             self.eip712_hash_struct()
         }
+
+        fn encode_type(&self) -> &'static str {
+            "Agent(string source,bytes32 connectionId)"
+        }
+
+        fn message(&self) -> Value {
+            json!({
+                "source": self.source,
+                "connectionId": self.connectionId,
+            })
+        }
     }
 }
@@ -1,16 +1,15 @@
-use alloy::{primitives::B256, signers::{local::PrivateKeySigner, Signature, SignerSync}};
+use alloy::{primitives::B256, signers::Signature};
 
-use crate::{eip712::Eip712, errors::Error, prelude::*, signature::agent::l1};
+use crate::{
+    eip712::{Eip712, TypedData},
+    helpers::Chain,
+    prelude::*,
+    signature::{agent::l1, signer::HyperliquidSigner},
+};
 
-#[cfg(not(feature = "testnet"))]
-const SOURCE: &str = "a";
-
-#[cfg(feature = "testnet")]
-const SOURCE: &str = "b";
-
-pub fn encode_l1_action(connection_id: B256) -> Result<B256> {
+pub fn encode_l1_action(connection_id: B256, chain: Chain) -> Result<B256> {
     let payload = l1::Agent {
-        source: SOURCE.to_string(),
+        source: chain.source().to_string(),
         connectionId: connection_id,
     };
     let encoded = payload.eip712_signing_hash();
@@ -18,24 +17,32 @@ pub fn encode_l1_action(connection_id: B256) -> Result<B256> {
     Ok(action)
 }
 
-pub(crate) fn sign_l1_action(
-    wallet: &PrivateKeySigner,
+/// The typed data whose digest is [`encode_l1_action`].
+pub fn l1_typed_data(connection_id: B256, chain: Chain) -> TypedData {
+    l1::Agent {
+        source: chain.source().to_string(),
+        connectionId: connection_id,
+    }
+    .typed_data()
+}
+
+pub(crate) async fn sign_l1_action<S: HyperliquidSigner>(
+    signer: &S,
     connection_id: B256,
-    is_mainnet: bool,
+    chain: Chain,
 ) -> Result<Signature> {
-    let source = if is_mainnet { "a" } else { "b" }.to_string();
     let payload = l1::Agent {
-        source,
+        source: chain.source().to_string(),
         connectionId: connection_id,
     };
-    sign_typed_data(&payload, wallet)
+    sign_typed_data(&payload, signer).await
 }
 
-pub(crate) fn sign_typed_data<T: Eip712>(
+pub(crate) async fn sign_typed_data<T: Eip712, S: HyperliquidSigner>(
     payload: &T,
-    wallet: &PrivateKeySigner,
+    signer: &S,
 ) -> Result<Signature> {
-    wallet
-        .sign_hash_sync(&payload.eip712_signing_hash())
-        .map_err(|e| Error::SignatureFailure(e.to_string()))
+    signer
+        .sign_typed_data(&payload.typed_data(), payload.eip712_signing_hash())
+        .await
 }
@@ -1,2 +1,4 @@
 pub mod agent;
 pub mod create_signature;
+pub mod signer;
+pub mod verify;
@@ -0,0 +1,220 @@
+use std::future::Future;
+#[cfg(unix)]
+use std::path::PathBuf;
+
+use alloy::{
+    primitives::{Address, B256},
+    signers::{local::PrivateKeySigner, Signature, SignerSync},
+};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+#[cfg(unix)]
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::UnixStream,
+};
+
+use crate::{
+    eip712::TypedData,
+    errors::Error,
+    exchange::signed_action::{deserialize_sig, serialize_sig},
+    prelude::*,
+    req::HttpClient,
+};
+
+/// Produces the signatures the exchange client sends. Implemented for local keys and for
+/// [`RemoteSigner`], which keeps the key in a separate signing daemon.
+pub trait HyperliquidSigner: Send + Sync {
+    /// Address the signatures recover to.
+    fn address(&self) -> Address;
+
+    /// Signs the EIP-712 digest `hash` of `typed_data`. The document is passed along for
+    /// signers that inspect what they sign; local keys only use the digest.
+    fn sign_typed_data(
+        &self,
+        typed_data: &TypedData,
+        hash: B256,
+    ) -> impl Future<Output = Result<Signature>> + Send;
+}
+
+impl HyperliquidSigner for PrivateKeySigner {
+    fn address(&self) -> Address {
+        PrivateKeySigner::address(self)
+    }
+
+    async fn sign_typed_data(&self, _typed_data: &TypedData, hash: B256) -> Result<Signature> {
+        self.sign_hash_sync(&hash)
+            .map_err(|e| Error::SignatureFailure(e.to_string()))
+    }
+}
+
+/// Request sent to a signing daemon.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SignRequest {
+    pub address: Address,
+    pub hash: B256,
+    pub typed_data: TypedData,
+}
+
+/// Response of a signing daemon: the signature as `r`, `s` and `v`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct SignResponse {
+    #[serde(serialize_with = "serialize_sig", deserialize_with = "deserialize_sig")]
+    pub signature: Signature,
+}
+
+#[derive(Debug, Clone)]
+enum Transport {
+    Http(HttpClient),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+/// Signs through a daemon that holds the key, so it never enters the trading process.
+///
+/// Over HTTP the [`SignRequest`] is posted as JSON to `{base_url}/sign`. Over a Unix socket
+/// it is written as one line of JSON and the daemon answers with one line. Either way the
+/// answer is a [`SignResponse`], and the signature is checked against `address` before use.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    address: Address,
+    transport: Transport,
+}
+
+impl RemoteSigner {
+    pub fn http(address: Address, base_url: impl Into<String>, client: Option<Client>) -> Self {
+        RemoteSigner {
+            address,
+            transport: Transport::Http(HttpClient {
+                client: client.unwrap_or_default(),
+                base_url: base_url.into(),
+            }),
+        }
+    }
+
+    #[cfg(unix)]
+    pub fn unix(address: Address, path: impl Into<PathBuf>) -> Self {
+        RemoteSigner {
+            address,
+            transport: Transport::Unix(path.into()),
+        }
+    }
+
+    async fn request(&self, request: &SignRequest) -> Result<String> {
+        let body = serde_json::to_string(request).map_err(|e| Error::JsonParse(e.to_string()))?;
+        match &self.transport {
+            Transport::Http(http_client) => http_client.post("/sign", body).await,
+            #[cfg(unix)]
+            Transport::Unix(path) => {
+                let map_err = |e: std::io::Error| Error::GenericRequest(e.to_string());
+                let mut stream = UnixStream::connect(path).await.map_err(map_err)?;
+                stream.write_all(body.as_bytes()).await.map_err(map_err)?;
+                stream.write_all(b"\n").await.map_err(map_err)?;
+
+                let mut line = String::new();
+                BufReader::new(stream)
+                    .read_line(&mut line)
+                    .await
+                    .map_err(map_err)?;
+                Ok(line)
+            }
+        }
+    }
+}
+
+impl HyperliquidSigner for RemoteSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_typed_data(&self, typed_data: &TypedData, hash: B256) -> Result<Signature> {
+        let request = SignRequest {
+            address: self.address,
+            hash,
+            typed_data: typed_data.clone(),
+        };
+        let output = self.request(&request).await?;
+        let SignResponse { signature } =
+            serde_json::from_str(&output).map_err(|e| Error::JsonParse(e.to_string()))?;
+
+        let recovered = signature
+            .recover_address_from_prehash(&hash)
+            .map_err(|e| Error::SignatureFailure(e.to_string()))?;
+        if recovered != self.address {
+            return Err(Error::SignerMismatch {
+                expected: self.address,
+                recovered,
+            });
+        }
+        Ok(signature)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::{TcpListener, UnixListener},
+    };
+
+    use super::*;
+    use crate::{
+        helpers::Chain,
+        signature::create_signature::l1_typed_data,
+        test_support::{read_request, wallet, write_response},
+    };
+
+    fn sign(wallet: &PrivateKeySigner, request: &str) -> String {
+        let request: SignRequest = serde_json::from_str(request).unwrap();
+        let signature = wallet.sign_hash_sync(&request.hash).unwrap();
+        serde_json::to_string(&SignResponse { signature }).unwrap()
+    }
+
+    async fn serve_http(listener: TcpListener, wallet: PrivateKeySigner) {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let (_, body) = read_request(&mut stream).await;
+        write_response(&mut stream, &sign(&wallet, &body)).await;
+    }
+
+    async fn serve_unix(listener: UnixListener, wallet: PrivateKeySigner) {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut stream = BufReader::new(stream);
+        let mut line = String::new();
+        stream.read_line(&mut line).await.unwrap();
+        let response = sign(&wallet, &line);
+        stream
+            .get_mut()
+            .write_all(format!("{response}\n").as_bytes())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_remote_signers() -> Result<()> {
+        let wallet = wallet();
+        let typed_data = l1_typed_data(B256::repeat_byte(1), Chain::Testnet);
+        let hash = B256::repeat_byte(2);
+        let expected = wallet.sign_hash_sync(&hash).unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(serve_http(listener, wallet.clone()));
+        let signer = RemoteSigner::http(wallet.address(), url, None);
+        assert_eq!(signer.sign_typed_data(&typed_data, hash).await?, expected);
+
+        let path = std::env::temp_dir().join(format!("hl-signer-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        tokio::spawn(serve_unix(listener, wallet.clone()));
+        let signer = RemoteSigner::unix(Address::ZERO, &path);
+        assert!(matches!(
+            signer.sign_typed_data(&typed_data, hash).await,
+            Err(Error::SignerMismatch { .. })
+        ));
+        std::fs::remove_file(&path).unwrap();
+
+        Ok(())
+    }
+}
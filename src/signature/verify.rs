@@ -0,0 +1,163 @@
+use alloy::primitives::{Address, Signature, B256};
+
+use crate::{
+    errors::Error, exchange::hash_generator::Actions, helpers::Chain, prelude::*,
+    signature::create_signature::encode_l1_action,
+};
+
+fn recover(hash: B256, signature: &Signature) -> Result<Address> {
+    signature
+        .recover_address_from_prehash(&hash)
+        .map_err(|e| Error::SignatureFailure(e.to_string()))
+}
+
+/// Recovers the signer of an L1 action. A signature made for another nonce, vault, expiry or
+/// chain recovers to an unrelated address, so compare the result with the claimed user.
+pub fn verify_l1_action(
+    action: &Actions,
+    nonce: u64,
+    vault_address: Option<Address>,
+    expires_after: Option<u64>,
+    signature: &Signature,
+    chain: Chain,
+) -> Result<Address> {
+    let connection_id = action.hash(nonce, vault_address, expires_after)?;
+    recover(encode_l1_action(connection_id, chain)?, signature)
+}
+
+/// Recovers the signer of a user-signed EIP-712 action such as a transfer or withdrawal. Fails
+/// if the action is not user-signed or names another chain than `chain`.
+pub fn verify_user_action(
+    action: &Actions,
+    signature: &Signature,
+    chain: Chain,
+) -> Result<Address> {
+    let payload = action
+        .user_signed()
+        .ok_or_else(|| Error::Eip712("action is not user-signed".to_string()))?;
+
+    let message = payload.message();
+    let hyperliquid_chain = message["hyperliquidChain"].as_str().unwrap_or_default();
+    if hyperliquid_chain != chain.hyperliquid_chain() {
+        return Err(Error::ChainMismatch {
+            expected: chain.hyperliquid_chain().to_string(),
+            found: hyperliquid_chain.to_string(),
+        });
+    }
+
+    recover(payload.eip712_signing_hash(), signature)
+}
+
+/// Recovers the signer of any action, picking the signing scheme the exchange uses for it.
+/// User-signed actions carry their nonce in the signed payload, which has to match `nonce`.
+pub fn verify_action(
+    action: &Actions,
+    nonce: u64,
+    vault_address: Option<Address>,
+    expires_after: Option<u64>,
+    signature: &Signature,
+    chain: Chain,
+) -> Result<Address> {
+    if let Some(payload) = action.user_signed() {
+        let message = payload.message();
+        let signed_nonce = message
+            .get("nonce")
+            .or_else(|| message.get("time"))
+            .and_then(|nonce| nonce.as_u64())
+            .ok_or_else(|| Error::Eip712("user-signed action has no nonce".to_string()))?;
+        if signed_nonce != nonce {
+            return Err(Error::NonceMismatch {
+                expected: nonce,
+                found: signed_nonce,
+            });
+        }
+        verify_user_action(action, signature, chain)
+    } else {
+        verify_l1_action(
+            action,
+            nonce,
+            vault_address,
+            expires_after,
+            signature,
+            chain,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::signers::SignerSync;
+
+    use super::*;
+    use crate::{
+        exchange::actions::{ScheduleCancel, UsdSend},
+        test_support::wallet,
+    };
+
+    #[test]
+    fn test_verify_actions() -> Result<()> {
+        let wallet = wallet();
+
+        let action = Actions::ScheduleCancel(ScheduleCancel { time: None });
+        let hash = action.signing_hash(1, None, None, Chain::Mainnet)?;
+        let signature = wallet.sign_hash_sync(&hash).unwrap();
+        assert_eq!(
+            verify_l1_action(&action, 1, None, None, &signature, Chain::Mainnet)?,
+            wallet.address()
+        );
+        assert_ne!(
+            verify_l1_action(&action, 1, None, None, &signature, Chain::Testnet)?,
+            wallet.address()
+        );
+        assert_ne!(
+            verify_l1_action(&action, 2, None, None, &signature, Chain::Mainnet)?,
+            wallet.address()
+        );
+        assert_ne!(
+            verify_l1_action(&action, 1, None, Some(2), &signature, Chain::Mainnet)?,
+            wallet.address()
+        );
+
+        let action = Actions::UsdSend(UsdSend {
+            signature_chain_id: Chain::Testnet.signature_chain_id(),
+            hyperliquid_chain: Chain::Testnet.hyperliquid_chain().to_string(),
+            destination: "0x0d1d9635d0640821d15e323ac8adadfa9c111414".to_string(),
+            amount: "1".to_string(),
+            time: 1690393044548,
+        });
+        let hash = action.signing_hash(1690393044548, None, None, Chain::Testnet)?;
+        let signature = wallet.sign_hash_sync(&hash).unwrap();
+        assert_eq!(
+            verify_user_action(&action, &signature, Chain::Testnet)?,
+            wallet.address()
+        );
+        assert!(matches!(
+            verify_user_action(&action, &signature, Chain::Mainnet),
+            Err(Error::ChainMismatch { .. })
+        ));
+        assert_eq!(
+            verify_action(
+                &action,
+                1690393044548,
+                None,
+                None,
+                &signature,
+                Chain::Testnet
+            )?,
+            wallet.address()
+        );
+        assert!(matches!(
+            verify_action(
+                &action,
+                1690393044549,
+                None,
+                None,
+                &signature,
+                Chain::Testnet
+            ),
+            Err(Error::NonceMismatch { .. })
+        ));
+
+        Ok(())
+    }
+}
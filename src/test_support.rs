@@ -0,0 +1,187 @@
+//! Stand-in API, key and client fixtures shared by the unit tests.
+
+use std::sync::{Arc, Mutex};
+
+use alloy::signers::local::PrivateKeySigner;
+use reqwest::Client;
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+use crate::{
+    helpers::Chain, meta::Meta, nonce::NonceManager, req::HttpClient, AssetResolver, ExchangeClient,
+};
+
+/// Requests received by a stand-in API, as `(path, body)`.
+pub(crate) type Requests = Arc<Mutex<Vec<(String, Value)>>>;
+
+/// Key of the account the fixtures sign with.
+pub(crate) fn wallet() -> PrivateKeySigner {
+    "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+        .parse()
+        .unwrap()
+}
+
+/// Answers `/info` requests for a perp universe of BTC, ETH and the only-isolated HYPE, the
+/// builder dex `xyz` and an account short 1.5 ETH and long 2 `xyz:XYZ100`.
+pub(crate) fn info(request: &Value) -> Value {
+    match request["type"].as_str() {
+        Some("meta") if request["dex"] == "xyz" => json!({"universe": [
+            {"name": "xyz:XYZ100", "szDecimals": 2, "maxLeverage": 20, "onlyIsolated": true},
+        ]}),
+        Some("meta") => json!({"universe": [
+            {"name": "BTC", "szDecimals": 5, "maxLeverage": 40},
+            {"name": "ETH", "szDecimals": 4, "maxLeverage": 25},
+            {"name": "HYPE", "szDecimals": 2, "maxLeverage": 10, "onlyIsolated": true},
+        ]}),
+        Some("spotMeta") => json!({"universe": [], "tokens": []}),
+        Some("extraAgents") => json!([
+            {
+                "name": "bot",
+                "address": "0x0d1d9635d0640821d15e323ac8adadfa9c111414",
+                "validUntil": u64::MAX,
+            },
+            {
+                "name": "old",
+                "address": "0x0d1d9635d0640821d15e323ac8adadfa9c111415",
+                "validUntil": 1,
+            },
+        ]),
+        Some("clearinghouseState") => {
+            let summary = json!({
+                "accountValue": "10000.0",
+                "totalMarginUsed": "0.0",
+                "totalNtlPos": "0.0",
+                "totalRawUsd": "10000.0",
+            });
+            let position = |coin: &str, szi: &str| {
+                json!({"type": "oneWay", "position": {
+                    "coin": coin,
+                    "entryPx": null,
+                    "leverage": {"type": "cross", "value": 10},
+                    "liquidationPx": null,
+                    "marginUsed": "0.0",
+                    "positionValue": "0.0",
+                    "returnOnEquity": "0.0",
+                    "szi": szi,
+                    "unrealizedPnl": "0.0",
+                    "maxLeverage": 40,
+                    "cumFunding": {"allTime": "0.0", "sinceOpen": "0.0", "sinceChange": "0.0"},
+                }})
+            };
+            let asset_positions = if request["dex"] == "xyz" {
+                json!([position("xyz:XYZ100", "2.0")])
+            } else {
+                json!([position("BTC", "0.0"), position("ETH", "-1.5")])
+            };
+            json!({
+                "assetPositions": asset_positions,
+                "crossMarginSummary": summary,
+                "marginSummary": summary,
+                "withdrawable": "10000.0",
+            })
+        }
+        Some("allMids") if request["dex"] == "xyz" => json!({"xyz:XYZ100": "25.123"}),
+        Some("allMids") => json!({"BTC": "50000.5", "ETH": "3001.37"}),
+        Some("perpDexs") => json!([null, {
+            "name": "xyz",
+            "fullName": "XYZ",
+            "deployer": "0x0d1d9635d0640821d15e323ac8adadfa9c111414",
+            "oracleUpdater": null,
+        }]),
+        _ => Value::Null,
+    }
+}
+
+/// Reads one HTTP request, returning its path and body.
+pub(crate) async fn read_request(stream: &mut TcpStream) -> (String, String) {
+    let mut buf = Vec::new();
+    loop {
+        let mut chunk = [0u8; 4096];
+        let n = stream.read(&mut chunk).await.unwrap();
+        buf.extend_from_slice(&chunk[..n]);
+        let text = String::from_utf8_lossy(&buf).to_string();
+        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+            let len = head
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().unwrap())
+                })
+                .unwrap_or_default();
+            if body.len() >= len {
+                let path = head.split_whitespace().nth(1).unwrap().to_string();
+                return (path, body.to_string());
+            }
+        }
+    }
+}
+
+/// Writes `response` as the JSON body of an HTTP 200 reply.
+pub(crate) async fn write_response(stream: &mut TcpStream, response: &str) {
+    let reply = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+        response.len()
+    );
+    stream.write_all(reply.as_bytes()).await.unwrap();
+}
+
+/// Serves a stand-in API: `/info` requests are answered by `info`, `/exchange` requests
+/// with an ok `default` response.
+pub(crate) async fn serve(info: fn(&Value) -> Value) -> (String, Requests) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Requests::default();
+    let received = Arc::clone(&requests);
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let (path, body) = read_request(&mut stream).await;
+            let body: Value = serde_json::from_str(&body).unwrap_or_default();
+            let response = match path.as_str() {
+                "/info" => info(&body),
+                _ => json!({"status": "ok", "response": {"type": "default"}}),
+            };
+            received.lock().unwrap().push((path, body));
+            write_response(&mut stream, &response.to_string()).await;
+        }
+    });
+    (url, requests)
+}
+
+/// A testnet client for the stand-in API at `url`, with the assets `info` describes.
+pub(crate) fn client(url: &str) -> ExchangeClient {
+    let meta: Meta = serde_json::from_value(info(&json!({"type": "meta"}))).unwrap();
+    let xyz_meta: Meta =
+        serde_json::from_value(info(&json!({"type": "meta", "dex": "xyz"}))).unwrap();
+    let mut assets = AssetResolver::new(&meta, None);
+    assets.add_perp_dex(1, "xyz", &xyz_meta);
+    ExchangeClient {
+        http_client: HttpClient {
+            client: Client::new(),
+            base_url: url.to_string(),
+        },
+        wallet: wallet(),
+        assets: Arc::new(assets),
+        meta,
+        vault_address: None,
+        chain: Chain::Testnet,
+        account_address: None,
+        nonces: Arc::new(NonceManager::new()),
+        expires_after: None,
+    }
+}
+
+/// The `/exchange` bodies a stand-in API received.
+pub(crate) fn posted(requests: &Requests) -> Vec<Value> {
+    requests
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(path, _)| path == "/exchange")
+        .map(|(_, body)| body.clone())
+        .collect()
+}
@@ -3,12 +3,18 @@ use std::collections::HashMap;
 use alloy::primitives::Address;
 use serde::{Deserialize, Serialize};
 
-use crate::info::Leverage;
+use crate::{
+    cloid::Cloid,
+    decimal::{Px, Sz},
+    enums::{OrderStatus, Side},
+    info::Leverage,
+    prelude::*,
+};
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct Trade {
     pub coin: String,
-    pub side: String,
+    pub side: Side,
     pub px: String,
     pub sz: String,
     pub time: u64,
@@ -17,6 +23,16 @@ pub struct Trade {
     pub users: (String, String),
 }
 
+impl Trade {
+    pub fn price(&self) -> Result<Px> {
+        self.px.parse()
+    }
+
+    pub fn size(&self) -> Result<Sz> {
+        self.sz.parse()
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct BookLevel {
     pub px: String,
@@ -24,6 +40,16 @@ pub struct BookLevel {
     pub n: u64,
 }
 
+impl BookLevel {
+    pub fn price(&self) -> Result<Px> {
+        self.px.parse()
+    }
+
+    pub fn size(&self) -> Result<Sz> {
+        self.sz.parse()
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct L2BookData {
     pub coin: String,
@@ -40,7 +66,7 @@ pub struct AllMidsData {
 #[serde(rename_all = "camelCase")]
 pub struct TradeInfo {
     pub coin: String,
-    pub side: String,
+    pub side: Side,
     pub px: String,
     pub sz: String,
     pub time: u64,
@@ -49,13 +75,23 @@ pub struct TradeInfo {
     pub dir: String,
     pub closed_pnl: String,
     pub oid: u64,
-    pub cloid: Option<String>,
+    pub cloid: Option<Cloid>,
     pub crossed: bool,
     pub fee: String,
     pub fee_token: String,
     pub tid: u64,
 }
 
+impl TradeInfo {
+    pub fn price(&self) -> Result<Px> {
+        self.px.parse()
+    }
+
+    pub fn size(&self) -> Result<Sz> {
+        self.sz.parse()
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct UserFillsData {
@@ -64,6 +100,59 @@ pub struct UserFillsData {
     pub fills: Vec<TradeInfo>,
 }
 
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapSliceFill {
+    pub fill: TradeInfo,
+    pub twap_id: u64,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserTwapSliceFillsData {
+    pub is_snapshot: Option<bool>,
+    pub user: Address,
+    pub twap_slice_fills: Vec<TwapSliceFill>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapState {
+    pub coin: String,
+    pub user: Address,
+    pub side: Side,
+    pub sz: String,
+    pub executed_sz: String,
+    pub executed_ntl: String,
+    pub minutes: u32,
+    pub reduce_only: bool,
+    pub randomize: bool,
+    pub timestamp: u64,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TwapHistoryStatus {
+    /// `"activated"`, `"terminated"`, `"finished"` or `"error"`.
+    pub status: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TwapHistoryEntry {
+    pub state: TwapState,
+    pub status: TwapHistoryStatus,
+    pub time: u64,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserTwapHistoryData {
+    pub is_snapshot: Option<bool>,
+    pub user: Address,
+    pub history: Vec<TwapHistoryEntry>,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub enum UserData {
@@ -116,7 +205,7 @@ pub struct CandleData {
 #[serde(rename_all = "camelCase")]
 pub struct OrderUpdate {
     pub order: BasicOrder,
-    pub status: String,
+    pub status: OrderStatus,
     pub status_timestamp: u64,
 }
 
@@ -124,13 +213,13 @@ pub struct OrderUpdate {
 #[serde(rename_all = "camelCase")]
 pub struct BasicOrder {
     pub coin: String,
-    pub side: String,
+    pub side: Side,
     pub limit_px: String,
     pub sz: String,
     pub oid: u64,
     pub timestamp: u64,
     pub orig_sz: String,
-    pub cloid: Option<String>,
+    pub cloid: Option<Cloid>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
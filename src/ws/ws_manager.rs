@@ -26,9 +26,14 @@ use tokio_tungstenite::{
 };
 
 use crate::{
-    errors::Error, prelude::*, ws::{
-        ActiveAssetCtx, ActiveAssetData, ActiveSpotAssetCtx, AllMids, Bbo, Candle, L2Book, Notification, OrderUpdates, Trades, User, UserFills, UserFundings, UserNonFundingLedgerUpdates, WebData2
-    }
+    assets::{AssetInfo, AssetResolver},
+    errors::Error,
+    prelude::*,
+    ws::{
+        ActiveAssetCtx, ActiveAssetData, ActiveSpotAssetCtx, AllMids, Bbo, Candle, L2Book,
+        Notification, OrderUpdates, Trades, User, UserFills, UserFundings,
+        UserNonFundingLedgerUpdates, UserTwapHistory, UserTwapSliceFills, WebData2,
+    },
 };
 
 #[derive(Debug)]
@@ -64,6 +69,8 @@ pub enum Subscription {
     ActiveAssetCtx { coin: String },
     ActiveAssetData { user: Address, coin: String },
     Bbo { coin: String },
+    UserTwapSliceFills { user: Address },
+    UserTwapHistory { user: Address },
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -88,9 +95,32 @@ pub enum Message {
     ActiveAssetData(ActiveAssetData),
     ActiveSpotAssetCtx(ActiveSpotAssetCtx),
     Bbo(Bbo),
+    UserTwapSliceFills(UserTwapSliceFills),
+    UserTwapHistory(UserTwapHistory),
     Pong,
 }
 
+impl Message {
+    /// Coin the message is about, as named by the API (e.g. `"@107"` for a spot pair).
+    pub fn coin(&self) -> Option<&str> {
+        match self {
+            Message::Trades(trades) => trades.data.first().map(|trade| trade.coin.as_str()),
+            Message::L2Book(l2_book) => Some(&l2_book.data.coin),
+            Message::Candle(candle) => Some(&candle.data.coin),
+            Message::ActiveAssetCtx(ctx) => Some(&ctx.data.coin),
+            Message::ActiveSpotAssetCtx(ctx) => Some(&ctx.data.coin),
+            Message::ActiveAssetData(data) => Some(&data.data.coin),
+            Message::Bbo(bbo) => Some(&bbo.data.coin),
+            _ => None,
+        }
+    }
+
+    /// Resolves [`Message::coin`] to its asset index, size decimals and leverage limits.
+    pub fn asset<'a>(&self, assets: &'a AssetResolver) -> Option<&'a AssetInfo> {
+        self.coin().and_then(|coin| assets.info(coin))
+    }
+}
+
 #[derive(Serialize)]
 pub struct SubscriptionSendData<'a> {
     method: &'static str,
@@ -282,6 +312,18 @@ impl WsManager {
                 })
                 .map_err(|e| Error::JsonParse(e.to_string()))
             }
+            Message::UserTwapSliceFills(fills) => {
+                serde_json::to_string(&Subscription::UserTwapSliceFills {
+                    user: fills.data.user,
+                })
+                .map_err(|e| Error::JsonParse(e.to_string()))
+            }
+            Message::UserTwapHistory(history) => {
+                serde_json::to_string(&Subscription::UserTwapHistory {
+                    user: history.data.user,
+                })
+                .map_err(|e| Error::JsonParse(e.to_string()))
+            }
             Message::Notification(_) => Ok("notification".to_string()),
             Message::WebData2(web_data2) => serde_json::to_string(&Subscription::WebData2 {
                 user: web_data2.data.user,